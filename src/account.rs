@@ -0,0 +1,168 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::fs;
+
+use crate::cluster::cluster_url;
+use crate::keys::extract_current_program_id;
+use crate::layout::{display_value, Layouts};
+use crate::output;
+use crate::project::Project;
+use crate::rpc;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// `pinoc account <address>`: fetches an account and decodes it with the program's state
+/// structs. Without `type_name` the type is picked by data length among the structs, as long
+/// as the account is owned by the program.
+pub fn show_account(address: &str, type_name: Option<&str>, cluster: &str) -> Result<()> {
+    let project = Project::open(".")?;
+    let layouts = Layouts::load(project.root())?;
+    let url = cluster_url(cluster);
+
+    status!("🔎 Fetching {} from {}", address, url);
+    let account = rpc::get_account_info(&url, address)?
+        .ok_or_else(|| anyhow::anyhow!("Account {} not found on {}", address, url))?;
+
+    let program_id = program_id(&project);
+    let owned = program_id.as_deref() == Some(account.owner.as_str());
+
+    let type_name = match type_name {
+        Some(name) => {
+            if layouts.get(name).is_none() {
+                anyhow::bail!(
+                    "Unknown type '{}'. Types found in src/states/: {}",
+                    name,
+                    type_list(&layouts)
+                );
+            }
+            if !owned {
+                warning!(
+                    "⚠️  The account is owned by {}, not by the program",
+                    account.owner
+                );
+            }
+            Some(name.to_string())
+        }
+        None if !owned => {
+            warning!(
+                "⚠️  The account is owned by {}, not by the program; showing raw data",
+                account.owner
+            );
+            None
+        }
+        None => detect_type(&layouts, account.data.len()),
+    };
+
+    let fields = match &type_name {
+        Some(name) => Some(layouts.decode(name, &account.data)?),
+        None => None,
+    };
+
+    output::event(
+        "account",
+        json!({
+            "address": address,
+            "owner": account.owner,
+            "lamports": account.lamports,
+            "executable": account.executable,
+            "data_len": account.data.len(),
+            "type": type_name,
+            "fields": fields.as_ref().map(|fields| fields.iter().cloned().collect::<Map<String, Value>>()),
+            "data": fields.is_none().then(|| hex(&account.data)),
+        }),
+    );
+
+    out!("📦 Account: {}", address);
+    out!("   Owner: {}", account.owner);
+    out!(
+        "   Balance: {:.9} SOL ({} lamports)",
+        account.lamports as f64 / LAMPORTS_PER_SOL,
+        account.lamports
+    );
+    out!("   Data: {} bytes", account.data.len());
+
+    match (type_name, fields) {
+        (Some(name), Some(fields)) => {
+            out!();
+            out!("📋 {}:", name);
+            let width = fields
+                .iter()
+                .map(|(field, _)| field.len())
+                .max()
+                .unwrap_or(0);
+            // padding fields are noise for people, but kept in the JSON output
+            for (field, value) in fields.iter().filter(|(field, _)| !field.starts_with('_')) {
+                out!(
+                    "   {:<width$}  {}",
+                    field,
+                    display_value(value),
+                    width = width
+                );
+            }
+        }
+        _ => {
+            out!();
+            out!("   {}", hex(&account.data));
+        }
+    }
+
+    Ok(())
+}
+
+/// the `declare_id!` of the program, or the program keypair when there is none
+fn program_id(project: &Project) -> Option<String> {
+    fs::read_to_string(project.lib_path())
+        .ok()
+        .and_then(|lib_content| extract_current_program_id(&lib_content))
+        .or_else(|| project.program_id().ok())
+}
+
+/// picks the struct whose size matches the data, warning when none or several do
+fn detect_type(layouts: &Layouts, len: usize) -> Option<String> {
+    let candidates = layouts.with_len(len);
+    match candidates.as_slice() {
+        [layout] => {
+            verbose!("🧩 Detected type {} ({} bytes)", layout.name, len);
+            Some(layout.name.clone())
+        }
+        [] => {
+            warning!(
+                "⚠️  No state struct is {} bytes long; showing raw data. Types: {}",
+                len,
+                type_list(layouts)
+            );
+            None
+        }
+        layouts => {
+            warning!(
+                "⚠️  Several types are {} bytes long ({}); pick one with --type",
+                len,
+                layouts
+                    .iter()
+                    .map(|layout| layout.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            None
+        }
+    }
+}
+
+fn type_list(layouts: &Layouts) -> String {
+    if layouts.is_empty() {
+        return "none".to_string();
+    }
+    layouts
+        .structs
+        .values()
+        .map(|layout| match layouts.len(&layout.name) {
+            Some(len) => format!("{} ({} bytes)", layout.name, len),
+            None => layout.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
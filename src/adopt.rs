@@ -0,0 +1,260 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::compat::{self, Release};
+use crate::error::PinocError;
+use crate::keys::{extract_current_program_id, keypair_pubkey, update_declare_id};
+use crate::output;
+use crate::project::Project;
+
+const DEFAULT_PINOC_TOML: &str = r#"[provider]
+cluster = "localhost"
+wallet = "~/.config/solana/id.json"
+"#;
+
+/// turns an existing crate into a pinoc project without touching its program logic
+pub fn adopt_project(project_dir: &Path) -> Result<()> {
+    let cargo_toml_path = project_dir.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        anyhow::bail!(
+            "Cargo.toml not found in {}. Use 'pinoc init <project_name>' to create a new project.",
+            project_dir.display()
+        );
+    }
+
+    let content =
+        fs::read_to_string(&cargo_toml_path).with_context(|| "Failed to read Cargo.toml")?;
+    let mut manifest: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| "Failed to parse Cargo.toml")?;
+
+    let package_name = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            anyhow::anyhow!("Cargo.toml has no [package] name. Workspaces can't be adopted, run this in the program crate.")
+        })?;
+    status!("🧑🏻‍🍳 Adopting existing crate: {}", package_name);
+    status!();
+
+    if ensure_manifest_settings(&mut manifest) {
+        fs::write(&cargo_toml_path, manifest.to_string())
+            .with_context(|| "Failed to write Cargo.toml")?;
+        status!("📝 Updated Cargo.toml (crate-type and no-entrypoint feature)");
+    } else {
+        status!("✅ Cargo.toml already has the cdylib crate-type and no-entrypoint feature");
+    }
+
+    let pinoc_toml_path = project_dir.join("Pinoc.toml");
+    if pinoc_toml_path.exists() {
+        status!("✅ Pinoc.toml already exists");
+    } else {
+        fs::write(&pinoc_toml_path, DEFAULT_PINOC_TOML)
+            .with_context(|| "Failed to write Pinoc.toml")?;
+        status!("📝 Created Pinoc.toml");
+    }
+
+    let project = Project::open(project_dir)?;
+    let keypair_path = project.keypair_path();
+    if keypair_path.exists() {
+        status!("✅ Program keypair found: {}", keypair_path.display());
+    } else {
+        let deploy_dir = project.deploy_dir();
+        fs::create_dir_all(&deploy_dir)
+            .with_context(|| format!("Failed to create {}", deploy_dir.display()))?;
+        let output = Command::new("solana-keygen")
+            .arg("new")
+            .arg("-o")
+            .arg(&keypair_path)
+            .arg("--no-bip39-passphrase")
+            .output()
+            .map_err(PinocError::tool("solana-keygen"))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to generate keypair: {}", error);
+        }
+        status!("🔑 Generated program keypair: {}", keypair_path.display());
+    }
+    let program_id = keypair_pubkey(&keypair_path)?;
+
+    output::artifact("keypair", &keypair_path);
+    output::address("program", &program_id);
+
+    let lib_path = project.lib_path();
+    let pubkey_crate = declare_id_crate(compat::project_release(project_dir));
+    let has_pubkey_crate = manifest
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.get(pubkey_crate))
+        .is_some();
+    sync_declare_id(&lib_path, &program_id, pubkey_crate, has_pubkey_crate)?;
+
+    status!();
+    status!("✅ '{}' is now a pinoc project!", package_name);
+    status!("🔑 Program ID: {}", program_id);
+    status!("\n📋 Next steps:");
+    status!("$ pinoc build");
+    status!("$ pinoc deploy");
+    status!();
+
+    Ok(())
+}
+
+/// makes sure the crate builds as a program and a library, returning whether anything changed
+fn ensure_manifest_settings(manifest: &mut toml_edit::DocumentMut) -> bool {
+    let mut changed = false;
+
+    let lib = manifest["lib"].or_insert(toml_edit::table());
+    let crate_types = lib["crate-type"].or_insert(toml_edit::value(toml_edit::Array::new()));
+    if let Some(crate_types) = crate_types.as_array_mut() {
+        for crate_type in ["cdylib", "rlib"] {
+            if !crate_types.iter().any(|t| t.as_str() == Some(crate_type)) {
+                crate_types.push(crate_type);
+                changed = true;
+            }
+        }
+    }
+
+    let features = manifest["features"].or_insert(toml_edit::table());
+    if features.get("no-entrypoint").is_none() {
+        features["no-entrypoint"] = toml_edit::value(toml_edit::Array::new());
+        changed = true;
+    }
+
+    changed
+}
+
+/// the crate `declare_id!` comes from: `solana-address` since pinocchio 0.10, `pinocchio-pubkey`
+/// before it or when the release isn't known
+fn declare_id_crate(release: Option<&Release>) -> &'static str {
+    release
+        .and_then(|release| {
+            release
+                .dependencies
+                .iter()
+                .find(|dependency| {
+                    dependency.name == "pinocchio-pubkey" || dependency.name == "solana-address"
+                })
+                .map(|dependency| dependency.name)
+        })
+        .unwrap_or("pinocchio-pubkey")
+}
+
+/// updates the address in `declare_id!`, or adds the macro when the crate has none
+fn sync_declare_id(
+    lib_path: &Path,
+    program_id: &str,
+    pubkey_crate: &str,
+    has_pubkey_crate: bool,
+) -> Result<()> {
+    let content = fs::read_to_string(lib_path)
+        .with_context(|| format!("Failed to read {}", lib_path.display()))?;
+
+    match extract_current_program_id(&content) {
+        Some(current) if current == program_id => {
+            status!("✅ declare_id! already matches the program keypair");
+            return Ok(());
+        }
+        Some(current) => {
+            let updated = update_declare_id(&content, program_id).ok_or_else(|| {
+                anyhow::anyhow!("Could not update declare_id! in {}", lib_path.display())
+            })?;
+            fs::write(lib_path, updated)
+                .with_context(|| format!("Failed to write {}", lib_path.display()))?;
+            status!("🔄 Updated declare_id! ({} → {})", current, program_id);
+        }
+        None => {
+            fs::write(
+                lib_path,
+                insert_declare_id(&content, program_id, pubkey_crate),
+            )
+            .with_context(|| format!("Failed to write {}", lib_path.display()))?;
+            status!("📝 Added declare_id! to {}", lib_path.display());
+            if !has_pubkey_crate {
+                status!(
+                    "💡 declare_id! comes from {}, add it with: pinoc add {}",
+                    pubkey_crate,
+                    pubkey_crate
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// inserts the macro after the inner attributes and module docs, which have to stay first
+fn insert_declare_id(content: &str, program_id: &str, pubkey_crate: &str) -> String {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if !(trimmed.is_empty() || trimmed.starts_with("#![") || trimmed.starts_with("//!")) {
+            break;
+        }
+        offset += line.len();
+    }
+
+    format!(
+        "{}{}::declare_id!(\"{}\");\n\n{}",
+        &content[..offset],
+        pubkey_crate.replace('-', "_"),
+        program_id,
+        &content[offset..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: &str = "22222222222222222222222222222222222222222222";
+
+    #[test]
+    fn takes_declare_id_from_the_release() {
+        assert_eq!(
+            declare_id_crate(Some(compat::find_release("0.10").unwrap())),
+            "solana-address"
+        );
+        assert_eq!(
+            declare_id_crate(Some(compat::find_release("0.9").unwrap())),
+            "pinocchio-pubkey"
+        );
+        assert_eq!(declare_id_crate(None), "pinocchio-pubkey");
+    }
+
+    #[test]
+    fn inserts_declare_id_after_inner_attributes() {
+        let content = "//! my program\n#![no_std]\n\nuse pinocchio::AccountView;\n";
+        assert_eq!(
+            insert_declare_id(content, PROGRAM_ID, "solana-address"),
+            format!(
+                "//! my program\n#![no_std]\n\nsolana_address::declare_id!(\"{}\");\n\nuse pinocchio::AccountView;\n",
+                PROGRAM_ID
+            )
+        );
+    }
+
+    #[test]
+    fn adds_the_macro_of_the_project_series() {
+        let dir = std::env::temp_dir().join(format!("pinoc-adopt-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"p\"\n\n[dependencies]\npinocchio = \"0.10.1\"\n",
+        )
+        .unwrap();
+        let lib_path = dir.join("src/lib.rs");
+        fs::write(&lib_path, "#![no_std]\n").unwrap();
+
+        let pubkey_crate = declare_id_crate(compat::project_release(&dir));
+        sync_declare_id(&lib_path, PROGRAM_ID, pubkey_crate, false).unwrap();
+        let content = fs::read_to_string(&lib_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(content.contains(&format!("solana_address::declare_id!(\"{}\")", PROGRAM_ID)));
+    }
+}
@@ -0,0 +1,657 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cluster::cluster_url;
+use crate::config::expand_tilde;
+use crate::error::PinocError;
+use crate::layout::{block, read_sources, split_fields, FieldType, Layouts};
+use crate::output;
+use crate::pda::{
+    declared_program_id, decode_pubkey, find_program_address, parse_seed, state_seeds, SeedSource,
+};
+use crate::project::Project;
+use crate::rpc::{self, Execution};
+use crate::transaction::{self, AccountMeta, Instruction};
+
+/// how long `pinoc call` waits for the transaction to be confirmed
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// accounts filled in when they aren't passed with `--account`
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const RENT_SYSVAR: &str = "SysvarRent111111111111111111111111111111111";
+const CLOCK_SYSVAR: &str = "SysvarC1ock11111111111111111111111111111111";
+const INSTRUCTIONS_SYSVAR: &str = "Sysvar1nstructions1111111111111111111111111";
+
+/// an instruction of the program, as read from `src/instructions/`
+struct InstructionSource {
+    name: String,
+    discriminator: u8,
+    /// names from `let [payer_acc, state_acc, ...] = accounts`
+    accounts: Vec<String>,
+    /// the `#[repr(C)]` struct passed to `load_ix_data`
+    data: Option<String>,
+    body: String,
+}
+
+/// `pinoc call <instruction>`: sends one instruction of the program with `--arg name=value`
+/// data fields and `--account name=value` accounts, signed by the wallet
+pub fn call_instruction(
+    name: &str,
+    args: &[String],
+    accounts: &[String],
+    cluster: &str,
+    wallet_path: &str,
+    program: Option<&str>,
+) -> Result<()> {
+    let project = Project::open(".")?;
+    let src = project.root().join("src");
+    let sources = read_sources(&src)?;
+    let layouts = Layouts::from_sources(&src)?;
+
+    let program_id = match program {
+        Some(program) => program.to_string(),
+        None => declared_program_id(&project)?,
+    };
+    let program_bytes = decode_pubkey(&program_id)?;
+    let wallet = transaction::read_keypair(Path::new(&expand_tilde(wallet_path)?))?;
+    let wallet_pubkey = wallet.verifying_key().to_bytes();
+
+    let instruction = find_instruction(&sources, name)?;
+    let mut args = parse_pairs(args, "--arg")?;
+    let mut account_values = parse_pairs(accounts, "--account")?;
+
+    // the data struct without the `bump=auto` fields, which need the PDA
+    let mut data = Vec::new();
+    let mut field_bytes = BTreeMap::new();
+    let mut auto_bumps = Vec::new();
+    if let Some(data_name) = &instruction.data {
+        let layout = layouts.get(data_name).ok_or_else(|| {
+            anyhow::anyhow!("Can't find the #[repr(C)] struct {} in src/", data_name)
+        })?;
+        let len = layouts
+            .len(data_name)
+            .ok_or_else(|| anyhow::anyhow!("Can't compute the size of {}", data_name))?;
+        data = vec![0; len];
+
+        for (offset, field) in layouts.offsets(layout) {
+            if field.name.starts_with('_') {
+                continue;
+            }
+            let Some(value) = args.remove(&field.name) else {
+                anyhow::bail!(
+                    "Missing --arg {}=<value>. {} takes: {}",
+                    field.name,
+                    data_name,
+                    field_list(&layouts, data_name)
+                );
+            };
+            if value == "auto" && field.name.contains("bump") {
+                auto_bumps.push(offset);
+                continue;
+            }
+            let bytes = encode_arg(&layouts, &field.ty, &value, &wallet_pubkey)
+                .with_context(|| format!("Invalid value for --arg {}", field.name))?;
+            data[offset..offset + bytes.len()].copy_from_slice(&bytes);
+            field_bytes.insert(field.name.clone(), bytes);
+        }
+    }
+    if let Some(arg) = args.keys().next() {
+        anyhow::bail!(
+            "{} takes no argument '{}'{}",
+            instruction.name,
+            arg,
+            match &instruction.data {
+                Some(data_name) => format!(". Arguments: {}", field_list(&layouts, data_name)),
+                None => String::new(),
+            }
+        );
+    }
+
+    let mut metas = Vec::new();
+    let mut signers = vec![wallet];
+    let mut bump = None;
+    let mut labels = Vec::new();
+    for account in &instruction.accounts {
+        let label = account_label(account);
+        let value = account_values
+            .remove(&label)
+            .or_else(|| account_values.remove(account));
+
+        let (pubkey, is_signer, is_writable, source) = match value.as_deref() {
+            Some("wallet") => (wallet_pubkey, true, true, "wallet".to_string()),
+            Some(value) if value.starts_with("pda:") => {
+                let state = &value["pda:".len()..];
+                let (address, pda_bump) = derive_state_pda(
+                    project.root(),
+                    state,
+                    &field_bytes,
+                    &wallet_pubkey,
+                    &program_bytes,
+                )?;
+                bump.get_or_insert(pda_bump);
+                (
+                    address,
+                    false,
+                    true,
+                    format!("pda:{}, bump {}", state, pda_bump),
+                )
+            }
+            Some(value) if value.starts_with("keypair:") => {
+                let path = expand_tilde(&value["keypair:".len()..])?;
+                let key = transaction::read_keypair(Path::new(&path))?;
+                let pubkey = key.verifying_key().to_bytes();
+                signers.push(key);
+                (pubkey, true, true, format!("keypair {}", path))
+            }
+            Some(value) if value.starts_with("mut:") => (
+                decode_pubkey(&value["mut:".len()..])?,
+                false,
+                true,
+                "writable".to_string(),
+            ),
+            Some(value) => (decode_pubkey(value)?, false, false, String::new()),
+            None => match well_known(&label, &instruction.body) {
+                Some(address) => (decode_pubkey(address)?, false, false, "auto".to_string()),
+                None => anyhow::bail!(
+                    "Missing --account {}=<wallet|pda:State|keypair:path|address|mut:address>. {} takes: {}",
+                    label,
+                    instruction.name,
+                    instruction
+                        .accounts
+                        .iter()
+                        .map(|account| account_label(account))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+        };
+
+        metas.push(AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        });
+        labels.push((label, bs58::encode(pubkey).into_string(), source));
+    }
+    if let Some(account) = account_values.keys().next() {
+        anyhow::bail!("{} has no account '{}'", instruction.name, account);
+    }
+
+    if !auto_bumps.is_empty() {
+        let bump = bump.ok_or_else(|| {
+            anyhow::anyhow!("bump=auto needs a pda:<State> account to take the bump from")
+        })?;
+        for offset in auto_bumps {
+            data[offset] = bump;
+        }
+    }
+
+    let mut instruction_data = vec![instruction.discriminator];
+    instruction_data.extend_from_slice(&data);
+
+    let url = cluster_url(cluster);
+    status!("📞 Calling {} on {}", instruction.name, url);
+    status!("   Program: {}", program_id);
+    for (label, address, source) in &labels {
+        if source.is_empty() {
+            status!("   {}: {}", label, address);
+        } else {
+            status!("   {}: {} ({})", label, address, source);
+        }
+    }
+    verbose!(
+        "   Data: {}",
+        instruction_data
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    );
+    status!();
+
+    let blockhash = rpc::latest_blockhash(&url)?;
+    let transaction = transaction::sign(
+        &Instruction {
+            program_id: program_bytes,
+            accounts: metas,
+            data: instruction_data,
+        },
+        &signers,
+        &blockhash,
+    )?;
+
+    output::step_started("simulate");
+    let simulation = rpc::simulate_transaction(&url, &transaction)?;
+    if simulation.err.is_some() {
+        output::step_failed("simulate");
+        return report_failure(&instruction.name, None, &simulation, &sources);
+    }
+    output::step_finished("simulate");
+
+    output::step_started("send");
+    let signature = rpc::send_transaction(&url, &transaction)?;
+    output::event("transaction", json!({ "signature": signature }));
+    status!("📨 Sent transaction {}", signature);
+
+    let execution = rpc::confirm_transaction(&url, &signature, CONFIRM_TIMEOUT)?;
+    if execution.err.is_some() {
+        output::step_failed("send");
+        return report_failure(&instruction.name, Some(&signature), &execution, &sources);
+    }
+    output::step_finished("send");
+
+    output::event(
+        "call",
+        json!({
+            "instruction": instruction.name,
+            "signature": signature,
+            "success": true,
+            "logs": execution.logs,
+        }),
+    );
+    print_logs(&execution.logs);
+    status!("✅ {} succeeded", instruction.name);
+    out!("{}", signature);
+    Ok(())
+}
+
+/// prints the logs and the decoded error of a failed transaction, and fails
+fn report_failure(
+    name: &str,
+    signature: Option<&str>,
+    execution: &Execution,
+    sources: &[String],
+) -> Result<()> {
+    let err = execution.err.clone().unwrap_or(Value::Null);
+    let message = describe_error(&err, sources);
+
+    output::event(
+        "call",
+        json!({
+            "instruction": name,
+            "signature": signature,
+            "success": false,
+            "error": message,
+            "logs": execution.logs,
+        }),
+    );
+    print_logs(&execution.logs);
+
+    Err(PinocError::CommandFailed {
+        command: format!("pinoc call {}", name),
+        message: match signature {
+            Some(signature) => format!("transaction {} failed: {}", signature, message),
+            None => format!("simulation failed: {}", message),
+        },
+    }
+    .into())
+}
+
+fn print_logs(logs: &[String]) {
+    if logs.is_empty() {
+        return;
+    }
+    out!("📜 Logs:");
+    for log in logs {
+        out!("   {}", log);
+    }
+    out!();
+}
+
+/// turns `{"InstructionError":[0,{"Custom":2}]}` into the name of the program's error variant
+fn describe_error(err: &Value, sources: &[String]) -> String {
+    let Some(error) = err.pointer("/InstructionError/1") else {
+        return err.to_string();
+    };
+    match error.get("Custom").and_then(Value::as_u64) {
+        Some(code) => match custom_error(sources, code) {
+            Some((enum_name, variant)) => {
+                format!("{}::{} (custom error {})", enum_name, variant, code)
+            }
+            None => format!("custom error {}", code),
+        },
+        None => match error {
+            Value::String(error) => error.clone(),
+            error => error.to_string(),
+        },
+    }
+}
+
+/// finds the variant with discriminant `code` of the enum converted into `ProgramError`
+fn custom_error(sources: &[String], code: u64) -> Option<(String, String)> {
+    for source in sources {
+        let Some(start) = source.find("impl From<") else {
+            continue;
+        };
+        let rest = &source[start + "impl From<".len()..];
+        let Some((enum_name, rest)) = rest.split_once('>') else {
+            continue;
+        };
+        if !rest.trim_start().starts_with("for ProgramError") {
+            continue;
+        }
+        let enum_name = enum_name.trim();
+        for source in sources {
+            if let Some(body) = block(source, &format!("enum {} {{", enum_name)) {
+                return variants(body)
+                    .into_iter()
+                    .find(|(_, discriminant)| *discriminant == code)
+                    .map(|(variant, _)| (enum_name.to_string(), variant));
+            }
+        }
+    }
+    None
+}
+
+/// the variants of an enum body with their discriminants, counting from 0 or from `= n`
+fn variants(body: &str) -> Vec<(String, u64)> {
+    let mut variants = Vec::new();
+    let mut next = 0;
+    for variant in split_fields(body) {
+        let (name, discriminant) = match variant.split_once('=') {
+            Some((name, value)) => (name.trim(), value.trim().parse().unwrap_or(next)),
+            None => (variant.trim(), next),
+        };
+        let name = name.split(['(', '{', ' ']).next().unwrap_or(name);
+        variants.push((name.to_string(), discriminant));
+        next = discriminant + 1;
+    }
+    variants
+}
+
+fn find_instruction(sources: &[String], name: &str) -> Result<InstructionSource> {
+    let body = sources
+        .iter()
+        .find_map(|source| block(source, "enum ProgramInstruction {"))
+        .ok_or_else(|| anyhow::anyhow!("No ProgramInstruction enum found in src/"))?;
+    let variants = variants(body);
+
+    let wanted = name.replace(['_', '-'], "").to_lowercase();
+    let Some((variant, discriminator)) = variants
+        .iter()
+        .find(|(variant, _)| variant.to_lowercase() == wanted)
+    else {
+        anyhow::bail!(
+            "Unknown instruction '{}'. Instructions: {}",
+            name,
+            variants
+                .iter()
+                .map(|(variant, _)| snake_case(variant))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+
+    let function = snake_case(variant);
+    let header = format!("fn {}(", function);
+    // the processor takes the accounts slice, unlike state methods with the same name
+    let body = sources
+        .iter()
+        .flat_map(|source| {
+            source
+                .match_indices(&header)
+                .map(|(start, _)| &source[start..])
+        })
+        .filter(|function| {
+            function
+                .find(')')
+                .is_some_and(|end| takes_accounts(&function[header.len()..end]))
+        })
+        .find_map(|function| block(function, &header))
+        .ok_or_else(|| anyhow::anyhow!("Can't find fn {}(accounts, data) in src/", function))?;
+
+    let accounts = body
+        .find("let [")
+        .and_then(|start| {
+            let list = &body[start + "let [".len()..];
+            let end = list.find(']')?;
+            list[end..]
+                .trim_start_matches(']')
+                .trim_start()
+                .starts_with("= accounts")
+                .then(|| &list[..end])
+        })
+        .map(|list| split_fields(list).into_iter().map(str::to_string).collect())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "fn {} has no `let [..] = accounts` to take the accounts from",
+                function
+            )
+        })?;
+
+    let data = body.find("load_ix_data::<").and_then(|start| {
+        let rest = &body[start + "load_ix_data::<".len()..];
+        Some(rest[..rest.find('>')?].trim().to_string())
+    });
+
+    Ok(InstructionSource {
+        name: function,
+        discriminator: u8::try_from(*discriminator)
+            .with_context(|| format!("{} has a discriminator above 255", variant))?,
+        accounts,
+        data,
+        body: body.to_string(),
+    })
+}
+
+/// whether a function's parameters include the accounts slice, `&[AccountInfo]` before
+/// pinocchio 0.10 and `&[AccountView]` after, or a parameter named `accounts`
+fn takes_accounts(params: &str) -> bool {
+    params.split(',').any(|param| {
+        let param = param.trim();
+        param.contains("[AccountInfo]")
+            || param.contains("[AccountView]")
+            || param
+                .split_once(':')
+                .is_some_and(|(name, _)| name.trim() == "accounts")
+    })
+}
+
+/// derives the address of a state from its `validate_pda` seeds: parameters named like a data
+/// field take its value, other pubkeys default to the wallet
+fn derive_state_pda(
+    root: &Path,
+    state: &str,
+    field_bytes: &BTreeMap<String, Vec<u8>>,
+    wallet: &[u8; 32],
+    program_id: &[u8; 32],
+) -> Result<([u8; 32], u8)> {
+    let state_seeds = state_seeds(root, state)?;
+    let mut seeds = Vec::new();
+    for seed in &state_seeds.seeds {
+        seeds.push(match seed {
+            SeedSource::Literal(bytes) => bytes.clone(),
+            SeedSource::Param { name, ty } => match (field_bytes.get(name), ty) {
+                (Some(bytes), _) => bytes.clone(),
+                (None, FieldType::Pubkey) => wallet.to_vec(),
+                (None, _) => anyhow::bail!(
+                    "The '{}' seed of {} needs --arg {}=<value>",
+                    name,
+                    state,
+                    name
+                ),
+            },
+        });
+    }
+    find_program_address(&seeds, program_id)
+}
+
+/// encodes an `--arg` value for a field: a pubkey or `wallet`, a number, a bool, or any
+/// `kind:value` seed such as `hex:0102` or `u64:5` for byte arrays
+fn encode_arg(
+    layouts: &Layouts,
+    ty: &FieldType,
+    value: &str,
+    wallet: &[u8; 32],
+) -> Result<Vec<u8>> {
+    let size = layouts
+        .size_of(ty)
+        .ok_or_else(|| anyhow::anyhow!("unknown type"))?;
+
+    let bytes = match ty {
+        FieldType::Pubkey if value == "wallet" => wallet.to_vec(),
+        FieldType::Bool => match value {
+            "true" | "1" => vec![1],
+            "false" | "0" => vec![0],
+            _ => anyhow::bail!("expected true or false, got '{}'", value),
+        },
+        _ if value.contains(':') => parse_seed(value)?,
+        FieldType::Pubkey => decode_pubkey(value)?.to_vec(),
+        FieldType::Unsigned(size) => parse_seed(&format!("u{}:{}", size * 8, value))?,
+        FieldType::Signed(size) => parse_seed(&format!("i{}:{}", size * 8, value))?,
+        // amounts are often stored as [u8; 8]
+        FieldType::Array(item, len)
+            if **item == FieldType::Unsigned(1) && matches!(len, 1 | 2 | 4 | 8 | 16) =>
+        {
+            parse_seed(&format!("u{}:{}", len * 8, value))?
+        }
+        _ => anyhow::bail!("pass '{}' as hex:<bytes>", value),
+    };
+
+    if bytes.len() != size {
+        anyhow::bail!("expected {} bytes, got {}", size, bytes.len());
+    }
+    Ok(bytes)
+}
+
+/// programs and sysvars an instruction names by convention
+fn well_known(label: &str, body: &str) -> Option<&'static str> {
+    Some(match label {
+        "system_program" => SYSTEM_PROGRAM,
+        "token_program" if body.contains("TOKEN_2022") || body.contains("token_2022") => {
+            TOKEN_2022_PROGRAM
+        }
+        "token_program" => TOKEN_PROGRAM,
+        "token_2022_program" => TOKEN_2022_PROGRAM,
+        "associated_token_program" | "ata_program" => ASSOCIATED_TOKEN_PROGRAM,
+        "rent" | "sysvar_rent" | "rent_sysvar" => RENT_SYSVAR,
+        "clock" | "sysvar_clock" | "clock_sysvar" => CLOCK_SYSVAR,
+        "instructions" | "sysvar_instructions" | "instructions_sysvar" => INSTRUCTIONS_SYSVAR,
+        _ => return None,
+    })
+}
+
+/// `payer_acc` and `_system_program` become `payer` and `system_program`
+fn account_label(account: &str) -> String {
+    let account = account.trim_start_matches('_');
+    account
+        .strip_suffix("_acc")
+        .or_else(|| account.strip_suffix("_account"))
+        .unwrap_or(account)
+        .to_string()
+}
+
+fn field_list(layouts: &Layouts, name: &str) -> String {
+    layouts
+        .get(name)
+        .map(|layout| {
+            layout
+                .fields
+                .iter()
+                .filter(|field| !field.name.starts_with('_'))
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+fn parse_pairs(pairs: &[String], flag: &str) -> Result<BTreeMap<String, String>> {
+    let mut map = BTreeMap::new();
+    for pair in pairs {
+        let Some((name, value)) = pair.split_once('=') else {
+            anyhow::bail!("Invalid {} '{}'. Use {} name=value", flag, pair, flag);
+        };
+        if map
+            .insert(name.trim().to_string(), value.trim().to_string())
+            .is_some()
+        {
+            anyhow::bail!("{} {} is given twice", flag, name);
+        }
+    }
+    Ok(map)
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(variants: &[(String, u64)]) -> Vec<(&str, u64)> {
+        variants
+            .iter()
+            .map(|(name, discriminant)| (name.as_str(), *discriminant))
+            .collect()
+    }
+
+    #[test]
+    fn counts_variants_from_zero() {
+        let variants = variants("Initialize, Deposit, Withdraw,");
+        assert_eq!(
+            names(&variants),
+            [("Initialize", 0), ("Deposit", 1), ("Withdraw", 2)]
+        );
+    }
+
+    #[test]
+    fn continues_after_explicit_discriminants() {
+        let variants = variants("Initialize = 5, Deposit, Withdraw = 9, Close");
+        assert_eq!(
+            names(&variants),
+            [
+                ("Initialize", 5),
+                ("Deposit", 6),
+                ("Withdraw", 9),
+                ("Close", 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn names_tuple_and_struct_variants() {
+        let variants = variants(
+            "#[allow(dead_code)] Initialize(InitializeData), Deposit { amount: u64, flag: bool }, Close",
+        );
+        assert_eq!(
+            names(&variants),
+            [("Initialize", 0), ("Deposit", 1), ("Close", 2)]
+        );
+    }
+
+    #[test]
+    fn recognizes_processor_parameters() {
+        assert!(takes_accounts("accounts: &[AccountInfo], data: &[u8]"));
+        assert!(takes_accounts("accounts: &[AccountView], data: &[u8]"));
+        assert!(takes_accounts("data: &[u8], accounts: &'a [Account]"));
+        assert!(!takes_accounts(
+            "state_acc: &AccountView, ix_data: &Initialize"
+        ));
+        assert!(!takes_accounts("&self, owner: &Pubkey"));
+    }
+
+    #[test]
+    fn converts_variant_names_to_snake_case() {
+        assert_eq!(snake_case("Initialize"), "initialize");
+        assert_eq!(snake_case("InitializeMint2"), "initialize_mint2");
+        assert_eq!(snake_case("CloseAccount"), "close_account");
+    }
+}
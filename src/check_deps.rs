@@ -0,0 +1,384 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::ecosystem::find_known_crate;
+use crate::error::PinocError;
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    resolve: Option<Resolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    version: String,
+    features: BTreeMap<String, Vec<String>>,
+    targets: Vec<Target>,
+    dependencies: Vec<DependencySpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    kind: Vec<String>,
+    src_path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependencySpec {
+    name: String,
+    uses_default_features: bool,
+    features: Vec<String>,
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+    root: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeDep {
+    pkg: String,
+    dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepKind {
+    kind: Option<String>,
+}
+
+struct Finding {
+    problem: String,
+    hint: String,
+}
+
+/// `pinoc check-deps`: flags dependencies that need `std` or an allocator, which a
+/// `#![no_std]` program with `no_allocator!()` can't use
+pub fn check_deps() -> Result<()> {
+    if !Path::new("Cargo.toml").exists() {
+        anyhow::bail!(
+            "Cargo.toml not found. Please run this command from the project root directory."
+        );
+    }
+
+    let output = Command::new("cargo")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .output()
+        .map_err(PinocError::tool("cargo"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("cargo metadata failed: {}", error.trim());
+    }
+
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)
+        .with_context(|| "Failed to parse cargo metadata output")?;
+    let resolve = metadata
+        .resolve
+        .ok_or_else(|| anyhow::anyhow!("cargo metadata returned no dependency graph"))?;
+    let root = resolve.root.clone().ok_or_else(|| {
+        anyhow::anyhow!("No root package found. Run this in the program crate, not a workspace.")
+    })?;
+
+    let features = normal_features()?;
+    let packages: HashMap<&str, &Package> = metadata
+        .packages
+        .iter()
+        .map(|package| (package.id.as_str(), package))
+        .collect();
+    let nodes: HashMap<&str, &Node> = resolve
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    // only normal dependencies end up in the program; dev and build dependencies and
+    // everything behind a proc-macro run on the host
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([root.as_str()]);
+    while let Some(id) = queue.pop_front() {
+        let Some(node) = nodes.get(id) else {
+            continue;
+        };
+        for dep in &node.deps {
+            let normal = dep.dep_kinds.iter().any(|kind| kind.kind.is_none());
+            let is_proc_macro = packages.get(dep.pkg.as_str()).is_some_and(|package| {
+                package
+                    .targets
+                    .iter()
+                    .any(|target| target.kind.iter().any(|kind| kind == "proc-macro"))
+            });
+            if !normal || is_proc_macro || dep.pkg == root || parents.contains_key(dep.pkg.as_str())
+            {
+                continue;
+            }
+            parents.insert(&dep.pkg, id);
+            order.push(dep.pkg.as_str());
+            queue.push_back(&dep.pkg);
+        }
+    }
+
+    status!(
+        "🔍 Checking {} dependencies for no_std and no_allocator!() compatibility\n",
+        order.len()
+    );
+
+    let mut flagged = 0;
+    for id in &order {
+        let Some(package) = packages.get(id) else {
+            continue;
+        };
+        let enabled = features
+            .get(&(package.name.clone(), package.version.clone()))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let path = dependency_path(id, &root, &parents, &packages);
+        let findings = inspect(package, enabled, &path, &packages, &root);
+        if findings.is_empty() {
+            continue;
+        }
+
+        flagged += 1;
+        if path.len() > 2 {
+            out!(
+                "⚠️  {} {} (via {})",
+                package.name,
+                package.version,
+                path[1..path.len() - 1].join(" → ")
+            );
+        } else {
+            out!("⚠️  {} {}", package.name, package.version);
+        }
+        for finding in findings {
+            out!("   • {}", finding.problem);
+            out!("     💡 {}", finding.hint);
+        }
+        out!();
+    }
+
+    if flagged > 0 {
+        anyhow::bail!(
+            "{} of {} dependencies aren't compatible with a no_std program",
+            flagged,
+            order.len()
+        );
+    }
+
+    status!("✅ All dependencies are no_std compatible and don't use alloc");
+
+    Ok(())
+}
+
+/// the features of each package in a build without dev-dependencies. `cargo metadata`
+/// reports them unified with the dev-dependencies, which tests enable `std` through.
+fn normal_features() -> Result<HashMap<(String, String), Vec<String>>> {
+    let output = Command::new("cargo")
+        .arg("tree")
+        .arg("--edges")
+        .arg("normal")
+        .arg("--prefix")
+        .arg("none")
+        .arg("--format")
+        .arg("{p}|{f}")
+        .output()
+        .map_err(PinocError::tool("cargo"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("cargo tree failed: {}", error.trim());
+    }
+
+    let mut features = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((package, enabled)) = line.split_once('|') else {
+            continue;
+        };
+        let mut parts = package.split_whitespace();
+        let (Some(name), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        features.insert(
+            (
+                name.to_string(),
+                version.trim_start_matches('v').to_string(),
+            ),
+            enabled
+                .split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+
+    Ok(features)
+}
+
+/// the package names from the root crate down to `id`
+fn dependency_path<'a>(
+    id: &'a str,
+    root: &str,
+    parents: &HashMap<&'a str, &'a str>,
+    packages: &HashMap<&str, &'a Package>,
+) -> Vec<&'a str> {
+    let mut path = Vec::new();
+    let mut current = Some(id);
+    while let Some(id) = current {
+        if let Some(package) = packages.get(id) {
+            path.push(package.name.as_str());
+        }
+        if id == root {
+            break;
+        }
+        current = parents.get(id).copied();
+    }
+    path.reverse();
+    path
+}
+
+fn inspect(
+    package: &Package,
+    features: &[String],
+    path: &[&str],
+    packages: &HashMap<&str, &Package>,
+    root: &str,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let enabled = |feature: &str| features.iter().any(|f| f == feature);
+    let source = package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind.ends_with("lib")))
+        .and_then(|target| fs::read_to_string(&target.src_path).ok())
+        .unwrap_or_default();
+
+    if let Some(warning) = find_known_crate(&package.name).and_then(|known| known.warning) {
+        findings.push(Finding {
+            problem: warning.to_string(),
+            hint: format!("Remove {} or move it to [dev-dependencies]", path[1]),
+        });
+    }
+
+    if !source.contains("no_std") {
+        findings.push(Finding {
+            problem: "doesn't support no_std, so it links std".to_string(),
+            hint: if path.len() > 2 {
+                format!(
+                    "Pulled in by {}; look for a no_std alternative or a feature of {} that drops it",
+                    path[path.len() - 2],
+                    path[1]
+                )
+            } else {
+                "Look for a no_std alternative or move it to [dev-dependencies]".to_string()
+            },
+        });
+    } else if enabled("std") {
+        findings.push(Finding {
+            problem: "the `std` feature is enabled".to_string(),
+            hint: feature_hint(package, "std", path, packages, root),
+        });
+    } else if enabled("alloc") {
+        findings.push(Finding {
+            problem: "the `alloc` feature is enabled, which fails under no_allocator!()"
+                .to_string(),
+            hint: feature_hint(package, "alloc", path, packages, root),
+        });
+    } else if uses_alloc(&source) {
+        findings.push(Finding {
+            problem: "uses the alloc crate, which fails under no_allocator!()".to_string(),
+            hint:
+                "Look for a heapless alternative or remove no_allocator!() and set up an allocator"
+                    .to_string(),
+        });
+    }
+
+    findings
+}
+
+/// explains where a feature comes from and how to turn it off
+fn feature_hint(
+    package: &Package,
+    feature: &str,
+    path: &[&str],
+    packages: &HashMap<&str, &Package>,
+    root: &str,
+) -> String {
+    let direct = path.len() == 2;
+    let spec = packages.get(root).and_then(|root| {
+        root.dependencies
+            .iter()
+            .find(|spec| spec.name == package.name && spec.kind.is_none())
+    });
+
+    match spec {
+        Some(spec) if direct && spec.features.iter().any(|f| f == feature) => format!(
+            "Remove \"{}\" from the features of {} in Cargo.toml",
+            feature, package.name
+        ),
+        Some(spec) if direct && spec.uses_default_features && in_default(package, feature) => {
+            format!(
+                "Add `default-features = false` to {} in Cargo.toml",
+                package.name
+            )
+        }
+        _ if !direct => format!(
+            "Enabled through {}; disable its default features or its \"{}\" feature (see `cargo tree -e features -i {}`)",
+            path[1], feature, package.name
+        ),
+        _ => format!(
+            "Another dependency enables it, see `cargo tree -e features -i {}`",
+            package.name
+        ),
+    }
+}
+
+/// whether `default` turns on the feature, directly or through other features
+fn in_default(package: &Package, feature: &str) -> bool {
+    let mut pending = vec!["default"];
+    let mut seen = Vec::new();
+    while let Some(current) = pending.pop() {
+        if current == feature {
+            return true;
+        }
+        if seen.contains(&current) {
+            continue;
+        }
+        seen.push(current);
+        if let Some(enables) = package.features.get(current) {
+            pending.extend(enables.iter().map(String::as_str));
+        }
+    }
+    false
+}
+
+/// an `extern crate alloc` that isn't behind a `#[cfg(...)]`
+fn uses_alloc(source: &str) -> bool {
+    let mut previous = "";
+    for line in source.lines().map(str::trim) {
+        if line.starts_with("extern crate alloc") && !previous.starts_with("#[cfg") {
+            return true;
+        }
+        if !line.is_empty() {
+            previous = line;
+        }
+    }
+    false
+}
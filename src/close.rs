@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::PinocError;
+use crate::keys::keypair_pubkey;
+use crate::output;
+use crate::project::Project;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// subset of `solana program show --buffers --output json`
+#[derive(Debug, Deserialize)]
+struct BufferList {
+    buffers: Vec<BufferAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BufferAccount {
+    address: String,
+    lamports: u64,
+}
+
+pub fn close_program(
+    cluster_url: &str,
+    wallet_path: &str,
+    recipient: Option<&str>,
+    buffers_only: bool,
+) -> Result<()> {
+    status!("🗑️  Closing program accounts");
+
+    let wallet_pubkey = keypair_pubkey(Path::new(wallet_path))?;
+    let recipient = recipient.unwrap_or(&wallet_pubkey);
+
+    let program_id = if buffers_only {
+        None
+    } else {
+        let program_keypair = Project::open(".")?.keypair_path();
+        if !program_keypair.exists() {
+            anyhow::bail!(
+                "Program keypair not found: {}. Use --buffers-only to only close buffers.",
+                program_keypair.display()
+            );
+        }
+        Some(keypair_pubkey(&program_keypair)?)
+    };
+
+    let buffers = list_buffers(cluster_url, wallet_path)?;
+
+    status!("📋 Using configuration:");
+    status!("   Cluster: {}", cluster_url);
+    status!("   Authority: {}", wallet_pubkey);
+    status!("   Recipient: {}", recipient);
+    status!();
+
+    if let Some(program_id) = &program_id {
+        status!("   Program to close: {}", program_id);
+    }
+    status!("   Buffers to close: {}", buffers.len());
+    for buffer in &buffers {
+        status!(
+            "      {} ({:.4} SOL)",
+            buffer.address,
+            buffer.lamports as f64 / LAMPORTS_PER_SOL
+        );
+    }
+    status!();
+
+    if program_id.is_none() && buffers.is_empty() {
+        status!(
+            "✅ No buffers owned by {} on {}. Nothing to close.",
+            wallet_pubkey,
+            cluster_url
+        );
+        return Ok(());
+    }
+
+    if let Some(program_id) = &program_id {
+        status!(
+            "⚠️  Closing program {} on {} is permanent: the program ID can never be deployed again.",
+            program_id, cluster_url
+        );
+    }
+
+    if !confirm(program_id.as_deref(), cluster_url)? {
+        status!("❌ Aborted. Nothing was closed.");
+        return Ok(());
+    }
+
+    if !buffers.is_empty() {
+        let status = Command::new("solana")
+            .arg("program")
+            .arg("close")
+            .arg("--buffers")
+            .arg("--recipient")
+            .arg(recipient)
+            .arg("--url")
+            .arg(cluster_url)
+            .arg("--keypair")
+            .arg(wallet_path)
+            .stdout(output::child_stdout())
+            .status()
+            .map_err(PinocError::tool("solana"))?;
+
+        if !status.success() {
+            anyhow::bail!("Closing buffers failed with exit code: {:?}", status.code());
+        }
+        status!("✅ Closed {} buffer(s)", buffers.len());
+    }
+
+    if let Some(program_id) = &program_id {
+        let status = Command::new("solana")
+            .arg("program")
+            .arg("close")
+            .arg(program_id)
+            .arg("--bypass-warning")
+            .arg("--authority")
+            .arg(wallet_path)
+            .arg("--recipient")
+            .arg(recipient)
+            .arg("--url")
+            .arg(cluster_url)
+            .arg("--keypair")
+            .arg(wallet_path)
+            .stdout(output::child_stdout())
+            .status()
+            .map_err(PinocError::tool("solana"))?;
+
+        if !status.success() {
+            anyhow::bail!("Closing program failed with exit code: {:?}", status.code());
+        }
+        status!("✅ Closed program {}", program_id);
+    }
+
+    status!("💰 Reclaimed lamports were sent to {}", recipient);
+
+    Ok(())
+}
+
+fn list_buffers(cluster_url: &str, wallet_path: &str) -> Result<Vec<BufferAccount>> {
+    let output = Command::new("solana")
+        .arg("program")
+        .arg("show")
+        .arg("--buffers")
+        .arg("--url")
+        .arg(cluster_url)
+        .arg("--keypair")
+        .arg(wallet_path)
+        .arg("--output")
+        .arg("json")
+        .output()
+        .map_err(PinocError::tool("solana"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to list buffers on {}: {}",
+            cluster_url,
+            error.trim()
+        );
+    }
+
+    let list: BufferList = serde_json::from_slice(&output.stdout)
+        .with_context(|| "Failed to parse 'solana program show --buffers' output")?;
+
+    Ok(list.buffers)
+}
+
+/// asks the user to type the program ID (or `yes` when only buffers are closed)
+fn confirm(program_id: Option<&str>, cluster_url: &str) -> Result<bool> {
+    let expected = program_id.unwrap_or("yes");
+    match program_id {
+        Some(program_id) => output::prompt(format_args!(
+            "Type the program ID {} to close it on {}: ",
+            program_id, cluster_url
+        ))?,
+        None => output::prompt(format_args!(
+            "Type 'yes' to close the buffers on {}: ",
+            cluster_url
+        ))?,
+    }
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .with_context(|| "Failed to read confirmation")?;
+
+    Ok(answer.trim() == expected)
+}
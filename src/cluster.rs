@@ -0,0 +1,48 @@
+/// returns a stable name for a cluster moniker or RPC URL, used for per-cluster files
+pub fn cluster_name(cluster: &str) -> String {
+    match cluster {
+        "localhost" | "l" | "localnet" => return "localnet".to_string(),
+        "devnet" | "d" => return "devnet".to_string(),
+        "testnet" | "t" => return "testnet".to_string(),
+        "mainnet-beta" | "m" | "mainnet" => return "mainnet-beta".to_string(),
+        _ => {}
+    }
+
+    let host = cluster
+        .split("://")
+        .last()
+        .unwrap_or(cluster)
+        .split(['/', '?'])
+        .next()
+        .unwrap_or(cluster);
+    let hostname = host.split(':').next().unwrap_or(host);
+
+    match hostname {
+        "localhost" | "127.0.0.1" | "0.0.0.0" => "localnet".to_string(),
+        "api.devnet.solana.com" => "devnet".to_string(),
+        "api.testnet.solana.com" => "testnet".to_string(),
+        "api.mainnet-beta.solana.com" => "mainnet-beta".to_string(),
+        _ => host
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect(),
+    }
+}
+
+/// returns the RPC URL for a cluster moniker, leaving URLs untouched
+pub fn cluster_url(cluster: &str) -> String {
+    match cluster {
+        "localhost" | "l" | "localnet" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" | "mainnet" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
@@ -0,0 +1,711 @@
+use anyhow::{Context, Result};
+use similar::TextDiff;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::output;
+
+/// a crate that has to move in lockstep with pinocchio
+pub struct Dependency {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub features: &'static [&'static str],
+}
+
+/// a pinocchio release series and the companion and test crates known to work with it
+pub struct Release {
+    /// `major.minor`, the part of the version breaking changes happen in
+    pub series: &'static str,
+    pub pinocchio: &'static str,
+    pub dependencies: &'static [Dependency],
+    pub dev_dependencies: &'static [Dependency],
+    /// crates replaced by another crate in this release (old, new)
+    pub renamed: &'static [(&'static str, &'static str)],
+    /// paths rewritten when upgrading into this release, matched as whole words
+    pub rewrites: &'static [(&'static str, &'static str)],
+    /// identifiers renamed outside `use` declarations, matched as whole words
+    pub renamed_items: &'static [(&'static str, &'static str)],
+    /// changes too ambiguous to rewrite, listed for the user to make
+    pub manual_steps: &'static [&'static str],
+}
+
+const fn dep(name: &'static str, version: &'static str) -> Dependency {
+    Dependency {
+        name,
+        version,
+        features: &[],
+    }
+}
+
+/// known pinocchio releases, oldest first
+pub const RELEASES: &[Release] = &[
+    Release {
+        series: "0.8",
+        pinocchio: "0.8.4",
+        dependencies: &[
+            dep("pinocchio-pubkey", "0.2.4"),
+            dep("pinocchio-system", "0.2.3"),
+            dep("pinocchio-log", "0.4.0"),
+            dep("pinocchio-token", "0.3.0"),
+            dep("pinocchio-associated-token-account", "0.1.2"),
+        ],
+        dev_dependencies: &[
+            dep("solana-sdk", "2.3.0"),
+            dep("solana-program-runtime", "=2.3.1"),
+            dep("mollusk-svm", "0.3.0"),
+            dep("mollusk-svm-bencher", "0.3.0"),
+            dep("litesvm", "0.6.1"),
+        ],
+        renamed: &[],
+        rewrites: &[],
+        renamed_items: &[],
+        manual_steps: &[],
+    },
+    Release {
+        series: "0.9",
+        pinocchio: "0.9.2",
+        dependencies: &[
+            dep("pinocchio-pubkey", "0.3.0"),
+            dep("pinocchio-system", "0.3.0"),
+            dep("pinocchio-log", "0.5.0"),
+            dep("pinocchio-token", "0.4.0"),
+            dep("pinocchio-associated-token-account", "0.2.0"),
+        ],
+        dev_dependencies: &[
+            dep("solana-sdk", "2.3.0"),
+            dep("solana-program-runtime", "=2.3.1"),
+            dep("mollusk-svm", "0.4.1"),
+            dep("mollusk-svm-bencher", "0.4.1"),
+            dep("litesvm", "0.6.1"),
+        ],
+        renamed: &[],
+        rewrites: &[],
+        renamed_items: &[],
+        manual_steps: &[],
+    },
+    Release {
+        series: "0.10",
+        pinocchio: "0.10.1",
+        dependencies: &[
+            Dependency {
+                name: "solana-address",
+                version: "1.1",
+                features: &["decode"],
+            },
+            dep("pinocchio-system", "0.4.0"),
+            dep("pinocchio-log", "0.5.1"),
+            dep("pinocchio-token", "0.5.0"),
+            dep("pinocchio-associated-token-account", "0.3.0"),
+        ],
+        dev_dependencies: &[
+            dep("solana-sdk", "3.0.0"),
+            dep("solana-program-runtime", "=3.0.0"),
+            dep("mollusk-svm", "0.7.0"),
+            dep("mollusk-svm-bencher", "0.7.0"),
+            dep("litesvm", "0.8.1"),
+        ],
+        renamed: &[("pinocchio-pubkey", "solana-address")],
+        rewrites: &[
+            (
+                "pinocchio_pubkey::declare_id!",
+                "solana_address::declare_id!",
+            ),
+            ("pinocchio::account_info::AccountInfo", "pinocchio::AccountView"),
+            ("account_info::AccountInfo", "AccountView"),
+            ("program_error::ProgramError", "error::ProgramError"),
+            ("pinocchio::pubkey::Pubkey", "pinocchio::Address"),
+            ("pubkey::Pubkey", "Address"),
+        ],
+        renamed_items: &[("AccountInfo", "AccountView"), ("Pubkey", "Address")],
+        manual_steps: &[
+            "Call `.address()` instead of `.key()` on accounts",
+            "Replace `pinocchio::pubkey` functions with the `Address` methods, e.g. `Address::create_program_address`",
+            "Import `Seed` and `Signer` from `pinocchio::cpi` instead of `pinocchio::instruction`",
+            "Tests: solana-sdk 3.0 moved program and sysvar IDs to `solana-sdk-ids`",
+        ],
+    },
+];
+
+/// a file and its content before and after the upgrade
+#[derive(Debug)]
+struct FileChange {
+    path: PathBuf,
+    old: String,
+    new: String,
+}
+
+/// finds the release for a version such as `0.9`, `0.9.2` or `=0.9.2`
+pub fn find_release(version: &str) -> Result<&'static Release> {
+    let series = series_of(version);
+    RELEASES
+        .iter()
+        .find(|release| release.series == series)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unsupported pinocchio version '{}'. Supported versions: {}",
+                version,
+                RELEASES
+                    .iter()
+                    .map(|release| release.pinocchio)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// the release matching the project's `pinocchio` dependency, if it has a known one
+pub fn project_release(project_dir: &Path) -> Option<&'static Release> {
+    let content = fs::read_to_string(project_dir.join("Cargo.toml")).ok()?;
+    let manifest: toml_edit::DocumentMut = content.parse().ok()?;
+    let version = manifest
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.get("pinocchio"))
+        .and_then(dependency_version)?;
+    find_release(&version).ok()
+}
+
+pub fn latest_release() -> &'static Release {
+    &RELEASES[RELEASES.len() - 1]
+}
+
+/// `pinoc upgrade-deps`: bumps the project to `to` (the latest known release by default)
+pub fn upgrade_deps(to: Option<&str>, yes: bool, dry_run: bool) -> Result<()> {
+    let target = match to {
+        Some(version) => find_release(version)?,
+        None => latest_release(),
+    };
+
+    let (current, changes) = plan_upgrade(Path::new("."), target, true)?
+        .ok_or_else(|| anyhow::anyhow!("pinocchio is not a dependency in Cargo.toml"))?;
+
+    status!("📦 Upgrading pinocchio {} → {}", current, target.pinocchio);
+    status!();
+
+    if changes.is_empty() {
+        status!("✅ Dependencies and sources are already up to date.");
+        return Ok(());
+    }
+
+    for change in &changes {
+        let name = change.path.display().to_string();
+        let diff = TextDiff::from_lines(&change.old, &change.new)
+            .unified_diff()
+            .header(&name, &name)
+            .to_string();
+        out!("{}", diff.trim_end_matches('\n'));
+    }
+    status!();
+
+    let manual_steps = crossed_releases(&current, target)
+        .iter()
+        .flat_map(|release| release.manual_steps.iter())
+        .collect::<Vec<_>>();
+    if !manual_steps.is_empty() {
+        warning!("⚠️  These changes aren't made automatically:");
+        for step in manual_steps {
+            warning!("   - {}", step);
+        }
+        status!("💡 Review the diff, then run 'pinoc build' and 'pinoc test'.");
+        status!();
+    }
+
+    if dry_run {
+        status!("💡 Dry run, nothing was written. Run without --dry-run to apply.");
+        return Ok(());
+    }
+
+    if !yes && !confirm(changes.len())? {
+        status!("❌ Aborted. Nothing was changed.");
+        return Ok(());
+    }
+
+    write_changes(&changes)?;
+    status!("✅ Updated {} file(s)", changes.len());
+
+    Ok(())
+}
+
+/// aligns the dependencies of a freshly generated project with a release without asking;
+/// the template renders the sources for the release itself
+pub fn apply_release(project_dir: &Path, target: &'static Release) -> Result<()> {
+    // templates without a pinocchio dependency have nothing to align
+    let Some((_, changes)) = plan_upgrade(project_dir, target, false)? else {
+        return Ok(());
+    };
+
+    if !changes.is_empty() {
+        write_changes(&changes)?;
+        status!("📌 Using pinocchio {}", target.pinocchio);
+    }
+
+    Ok(())
+}
+
+/// computes the new Cargo.toml and, with `rewrite_sources`, the new sources, returning the
+/// current pinocchio version too; `None` when pinocchio isn't a dependency
+fn plan_upgrade(
+    project_dir: &Path,
+    target: &'static Release,
+    rewrite_sources: bool,
+) -> Result<Option<(String, Vec<FileChange>)>> {
+    let cargo_toml_path = project_dir.join("Cargo.toml");
+    let old_manifest = fs::read_to_string(&cargo_toml_path)
+        .with_context(|| "Cargo.toml not found. Please run this command from the project root.")?;
+    let mut manifest: toml_edit::DocumentMut = old_manifest
+        .parse()
+        .with_context(|| "Failed to parse Cargo.toml")?;
+
+    let Some(current_version) = manifest
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.get("pinocchio"))
+        .and_then(dependency_version)
+    else {
+        return Ok(None);
+    };
+    let current = find_release(&current_version)?;
+    if release_index(target) < release_index(current) {
+        anyhow::bail!(
+            "Downgrading pinocchio from {} to {} isn't supported.",
+            current_version,
+            target.pinocchio
+        );
+    }
+    let crossed = crossed_releases(&current_version, target);
+
+    if let Some(dependencies) = manifest
+        .get_mut("dependencies")
+        .and_then(|item| item.as_table_like_mut())
+    {
+        if let Some(pinocchio) = dependencies.get_mut("pinocchio") {
+            set_version(pinocchio, target.pinocchio);
+        }
+        for release in crossed {
+            for (old, new) in release.renamed {
+                if dependencies.remove(old).is_some() && dependencies.get(new).is_none() {
+                    if let Some(dependency) =
+                        release.dependencies.iter().find(|dep| dep.name == *new)
+                    {
+                        dependencies.insert(new, dependency_item(dependency));
+                    }
+                }
+            }
+        }
+        align_versions(dependencies, target.dependencies);
+    }
+
+    if let Some(dev_dependencies) = manifest
+        .get_mut("dev-dependencies")
+        .and_then(|item| item.as_table_like_mut())
+    {
+        align_versions(dev_dependencies, target.dev_dependencies);
+    }
+
+    let mut changes = Vec::new();
+    let new_manifest = manifest.to_string();
+    if new_manifest != old_manifest {
+        changes.push(FileChange {
+            path: cargo_toml_path,
+            old: old_manifest,
+            new: new_manifest,
+        });
+    }
+
+    if rewrite_sources {
+        for path in rust_files(&project_dir.join("src"))? {
+            let old = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let new = crossed.iter().fold(old.clone(), |source, release| {
+                rewrite_source(&source, release)
+            });
+            if new != old {
+                changes.push(FileChange { path, old, new });
+            }
+        }
+    }
+
+    Ok(Some((current_version, changes)))
+}
+
+/// the releases after `current` up to and including `target`
+fn crossed_releases(current: &str, target: &Release) -> &'static [Release] {
+    let current_index = find_release(current).map_or(0, release_index);
+    let target_index = release_index(target);
+    if target_index <= current_index {
+        return &[];
+    }
+    &RELEASES[current_index + 1..=target_index]
+}
+
+/// applies the path rewrites of `release` everywhere and its item renames outside `use`
+/// declarations, where renamed items are moved out of their module's group instead
+fn rewrite_source(source: &str, release: &Release) -> String {
+    let source = release
+        .rewrites
+        .iter()
+        .fold(source.to_string(), |source, (from, to)| {
+            replace_word(&source, from, to)
+        });
+
+    let mut rewritten = String::with_capacity(source.len());
+    let mut use_decl = String::new();
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if use_decl.is_empty() && !(trimmed.starts_with("use ") || trimmed.starts_with("pub use "))
+        {
+            rewritten.push_str(
+                &release
+                    .renamed_items
+                    .iter()
+                    .fold(line.to_string(), |line, (from, to)| {
+                        replace_word(&line, from, to)
+                    }),
+            );
+            continue;
+        }
+
+        use_decl.push_str(line);
+        if line.contains(';') {
+            rewritten.push_str(&split_use_groups(&use_decl, release));
+            use_decl.clear();
+        }
+    }
+    rewritten.push_str(&use_decl);
+    rewritten
+}
+
+/// moves items whose path is rewritten out of their module's group in a `use` declaration, so
+/// `pubkey::{self, Pubkey}` becomes `pubkey, Address` and the module stays imported
+fn split_use_groups(decl: &str, release: &Release) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut decl = decl.to_string();
+    for (from, to) in release.rewrites {
+        let Some((module, item)) = from.split_once("::") else {
+            continue;
+        };
+        if item.contains("::") || to.contains("::") {
+            continue;
+        }
+
+        let open = format!("{}::{{", module);
+        let mut search = 0;
+        while let Some(found) = decl[search..].find(&open) {
+            let start = search + found;
+            let inner_start = start + open.len();
+            search = inner_start;
+            if decl[..start].chars().next_back().is_some_and(is_ident) {
+                continue;
+            }
+            let Some(inner_end) = decl[inner_start..].find('}').map(|end| inner_start + end) else {
+                break;
+            };
+            let items: Vec<&str> = decl[inner_start..inner_end]
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect();
+            if items.iter().any(|item| item.contains('{')) || !items.contains(&item) {
+                continue;
+            }
+
+            let rest: Vec<&str> = items.into_iter().filter(|name| name != &item).collect();
+            let mut moved = match rest.as_slice() {
+                [] => String::new(),
+                ["self"] => format!("{}, ", module),
+                rest => format!("{}::{{{}}}, ", module, rest.join(", ")),
+            };
+            moved.push_str(to);
+            // at the top level, `use parent::module::{..}` needs a group for the moved item
+            let depth = decl[..start].matches('{').count() - decl[..start].matches('}').count();
+            if depth == 0 {
+                moved = format!("{{{}}}", moved);
+            }
+            decl.replace_range(start..=inner_end, &moved);
+            search = start + moved.len();
+        }
+    }
+    decl
+}
+
+/// replaces `from` where it isn't part of a longer identifier
+fn replace_word(source: &str, from: &str, to: &str) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(index) = rest.find(from) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + from.len()..].chars().next();
+        let starts_word = !from.starts_with(is_ident) || !before.is_some_and(is_ident);
+        let ends_word = !from.ends_with(is_ident) || !after.is_some_and(is_ident);
+
+        result.push_str(&rest[..index]);
+        result.push_str(if starts_word && ends_word { to } else { from });
+        rest = &rest[index + from.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn align_versions(table: &mut dyn toml_edit::TableLike, known: &[Dependency]) {
+    for dependency in known {
+        if let Some(item) = table.get_mut(dependency.name) {
+            set_version(item, dependency.version);
+        }
+    }
+}
+
+/// sets the version of a `name = "x"` or `name = { version = "x", ... }` dependency
+fn set_version(item: &mut toml_edit::Item, version: &str) {
+    if let Some(value) = item.as_value_mut().filter(|value| value.is_str()) {
+        // keep comments and spacing around the version
+        let decor = value.decor().clone();
+        *value = version.into();
+        *value.decor_mut() = decor;
+    } else if let Some(table) = item.as_table_like_mut() {
+        table.insert("version", toml_edit::value(version));
+    }
+}
+
+fn dependency_item(dependency: &Dependency) -> toml_edit::Item {
+    if dependency.features.is_empty() {
+        return toml_edit::value(dependency.version);
+    }
+
+    let mut table = toml_edit::InlineTable::new();
+    table.insert("version", dependency.version.into());
+    table.insert(
+        "features",
+        toml_edit::Value::Array(dependency.features.iter().copied().collect()),
+    );
+    toml_edit::value(table)
+}
+
+fn dependency_version(item: &toml_edit::Item) -> Option<String> {
+    item.as_str()
+        .or_else(|| item.get("version").and_then(|version| version.as_str()))
+        .map(str::to_string)
+}
+
+/// `=0.9.2` -> `0.9`
+pub(crate) fn series_of(version: &str) -> String {
+    let version = version.trim_start_matches(['=', '^', '~', ' ']);
+    version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
+fn release_index(release: &Release) -> usize {
+    RELEASES
+        .iter()
+        .position(|known| known.series == release.series)
+        .unwrap_or(0)
+}
+
+fn rust_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(rust_files(&path)?);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+fn write_changes(changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        fs::write(&change.path, &change.new)
+            .with_context(|| format!("Failed to write {}", change.path.display()))?;
+    }
+    Ok(())
+}
+
+fn confirm(files: usize) -> Result<bool> {
+    output::prompt(format_args!(
+        "Apply these changes to {} file(s)? [y/N]: ",
+        files
+    ))?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .with_context(|| "Failed to read confirmation")?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a scratch project with the given Cargo.toml and src/lib.rs
+    fn project(name: &str, manifest: &str, lib: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("pinoc-compat-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        fs::write(dir.join("src/lib.rs"), lib).unwrap();
+        dir
+    }
+
+    const MANIFEST_0_8: &str = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+pinocchio = "0.8.4"
+pinocchio-pubkey = "0.2.4"
+pinocchio-system = { version = "0.2.3", features = [] }
+
+[dev-dependencies]
+mollusk-svm = "0.3.0"
+"#;
+
+    #[test]
+    fn plan_upgrade_bumps_and_renames_dependencies() {
+        let dir = project("deps", MANIFEST_0_8, "");
+        let (current, changes) = plan_upgrade(&dir, find_release("0.10").unwrap(), false)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(current, "0.8.4");
+        assert_eq!(changes.len(), 1);
+        let manifest = &changes[0].new;
+        assert!(manifest.contains(r#"pinocchio = "0.10.1""#));
+        assert!(!manifest.contains("pinocchio-pubkey"));
+        assert!(manifest.contains(r#"solana-address = { version = "1.1", features = ["decode"] }"#));
+        assert!(manifest.contains(r#"pinocchio-system = { version = "0.4.0", features = [] }"#));
+        assert!(manifest.contains(r#"mollusk-svm = "0.7.0""#));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn plan_upgrade_rewrites_whole_words_outside_use_declarations() {
+        let lib = r#"use pinocchio::{account_info::AccountInfo, pubkey::{self, Pubkey}};
+
+pinocchio_pubkey::declare_id!("11111111111111111111111111111111");
+
+pub enum PubkeyError {}
+
+pub fn check(account: &AccountInfo, owner: &Pubkey, map: &Map) -> bool {
+    map.key() == account.key()
+}
+"#;
+        let dir = project("sources", MANIFEST_0_8, lib);
+        let (_, changes) = plan_upgrade(&dir, find_release("0.10").unwrap(), true)
+            .unwrap()
+            .unwrap();
+
+        let source = &changes
+            .iter()
+            .find(|change| change.path.ends_with("lib.rs"))
+            .unwrap()
+            .new;
+        assert_eq!(
+            source,
+            r#"use pinocchio::{AccountView, pubkey, Address};
+
+solana_address::declare_id!("11111111111111111111111111111111");
+
+pub enum PubkeyError {}
+
+pub fn check(account: &AccountView, owner: &Address, map: &Map) -> bool {
+    map.key() == account.key()
+}
+"#
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rewrite_source_moves_renamed_items_out_of_use_groups() {
+        let release = find_release("0.10").unwrap();
+        let source = "use pinocchio::pubkey::{self, Pubkey};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    pubkey::Pubkey as Key,
+};
+use crate::pubkey::{Pubkey2};
+";
+        assert_eq!(
+            rewrite_source(source, release),
+            "use pinocchio::{pubkey, Address};
+use pinocchio::{
+    account_info::{Ref}, AccountView,
+    Address as Key,
+};
+use crate::pubkey::{Pubkey2};
+"
+        );
+    }
+
+    #[test]
+    fn plan_upgrade_leaves_sources_alone_when_not_asked() {
+        let dir = project("init", MANIFEST_0_8, "use pinocchio::pubkey::Pubkey;\n");
+        let (_, changes) = plan_upgrade(&dir, find_release("0.10").unwrap(), false)
+            .unwrap()
+            .unwrap();
+
+        assert!(changes
+            .iter()
+            .all(|change| change.path.ends_with("Cargo.toml")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn plan_upgrade_within_a_release_has_no_source_changes() {
+        let manifest = MANIFEST_0_8.replace("0.8.4", "0.9.2");
+        let dir = project("same", &manifest, "use pinocchio::pubkey::Pubkey;\n");
+        let (_, changes) = plan_upgrade(&dir, find_release("0.9").unwrap(), true)
+            .unwrap()
+            .unwrap();
+
+        assert!(changes
+            .iter()
+            .all(|change| change.path.ends_with("Cargo.toml")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn plan_upgrade_rejects_downgrades() {
+        let manifest = MANIFEST_0_8.replace("0.8.4", "0.10.1");
+        let dir = project("downgrade", &manifest, "");
+        assert!(plan_upgrade(&dir, find_release("0.8").unwrap(), true).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn plan_upgrade_without_pinocchio() {
+        let dir = project("none", "[package]\nname = \"demo\"\n", "");
+        assert!(plan_upgrade(&dir, latest_release(), true)
+            .unwrap()
+            .is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn replace_word_skips_longer_identifiers() {
+        assert_eq!(
+            replace_word("Pubkey PubkeyError MyPubkey &Pubkey", "Pubkey", "Address"),
+            "Address PubkeyError MyPubkey &Address"
+        );
+        assert_eq!(
+            replace_word("pinocchio::pubkey::Pubkey", "pubkey::Pubkey", "Address"),
+            "pinocchio::Address"
+        );
+    }
+
+    #[test]
+    fn series_of_versions() {
+        assert_eq!(series_of("0.9.2"), "0.9");
+        assert_eq!(series_of("=0.10.1"), "0.10");
+        assert_eq!(series_of("^0.8"), "0.8");
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{PinocError, Result};
+
+pub const CONFIG_FILE: &str = "Pinoc.toml";
+
+/// the contents of a project's Pinoc.toml
+#[derive(Debug, Clone, Deserialize)]
+pub struct PinocConfig {
+    pub provider: ProviderConfig,
+    #[serde(default)]
+    pub toolchain: Option<ToolchainConfig>,
+    /// commands for `pinoc run <name>`, including the `pre-build`, `post-build`, `pre-deploy`
+    /// and `post-deploy` hooks
+    #[serde(default)]
+    pub scripts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderConfig {
+    pub cluster: String,
+    pub wallet: String,
+}
+
+/// toolchain pinned for verifiable builds
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolchainConfig {
+    pub build_sbf_version: String,
+    #[serde(default)]
+    pub platform_tools_version: Option<String>,
+}
+
+/// reads the Pinoc.toml in `project_dir`
+pub fn load(project_dir: &Path) -> Result<PinocConfig> {
+    let config_path = project_dir.join(CONFIG_FILE);
+    if !config_path.exists() {
+        return Err(PinocError::NotAProject {
+            dir: project_dir.to_path_buf(),
+            file: CONFIG_FILE,
+        });
+    }
+
+    let config_content = fs::read_to_string(&config_path).map_err(PinocError::io(&config_path))?;
+
+    toml::from_str(&config_content).map_err(|error| PinocError::InvalidConfig {
+        path: config_path,
+        message: error.message().to_string(),
+    })
+}
+
+/// expands a leading `~` to the home directory, as in the default wallet path
+pub fn expand_tilde(path: &str) -> Result<String> {
+    if path.starts_with("~") {
+        if let Some(home_dir) = dirs::home_dir() {
+            return Ok(path.replacen("~", home_dir.to_str().unwrap_or(""), 1));
+        } else {
+            return Err(
+                anyhow::anyhow!("Could not determine the home directory to expand '~'").into(),
+            );
+        }
+    }
+    Ok(path.to_string())
+}
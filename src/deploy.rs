@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::{self, expand_tilde};
+use crate::error::PinocError;
+use crate::keys::keypair_pubkey;
+use crate::output;
+use crate::project::Project;
+use crate::{deployments, preflight};
+
+/// subset of `solana program deploy --output json`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeployOutput {
+    program_id: String,
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+pub fn deploy_program(
+    cluster: Option<&str>,
+    wallet: Option<&str>,
+    skip_checks: bool,
+) -> Result<()> {
+    status!("Deploying program");
+
+    let config = config::load(Path::new("."))?;
+
+    let cluster_url = cluster.unwrap_or(&config.provider.cluster);
+    let wallet_path = wallet.unwrap_or(&config.provider.wallet);
+
+    status!("📋 Using configuration:");
+    status!("   Cluster: {}", cluster_url);
+    status!("   Wallet: {}", wallet_path);
+
+    let so_path = Project::open(".")?.find_program_so()?;
+    let wallet_path = expand_tilde(wallet_path)?;
+
+    if !skip_checks {
+        preflight::run_deploy_checks(cluster_url, &wallet_path, &so_path)?;
+    }
+
+    let mut deploy_cmd = Command::new("solana");
+    deploy_cmd
+        .arg("program")
+        .arg("deploy")
+        .arg("--url")
+        .arg(cluster_url)
+        .arg("--keypair")
+        .arg(&wallet_path)
+        .arg("--output")
+        .arg("json")
+        .arg(&so_path);
+
+    output::command(&deploy_cmd);
+
+    // progress is reported on stderr, the json result on stdout
+    let output = deploy_cmd
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|child| child.wait_with_output())
+        .map_err(PinocError::tool("solana"))?;
+
+    if !output.status.success() {
+        return Err(PinocError::CommandFailed {
+            command: "solana program deploy".to_string(),
+            message: format!("exit code: {:?}", output.status.code()),
+        }
+        .into());
+    }
+
+    let deploy_output: DeployOutput = serde_json::from_slice(&output.stdout)
+        .with_context(|| "Failed to parse 'solana program deploy' output")?;
+
+    output::address("program", &deploy_output.program_id);
+    if let Some(signature) = &deploy_output.signature {
+        output::event("transaction", json!({ "signature": signature }));
+    }
+    status!("Program deployed successfully!");
+    out!("🔑 Program ID: {}", deploy_output.program_id);
+    if let Some(signature) = &deploy_output.signature {
+        out!("✍️  Signature: {}", signature);
+    }
+
+    let deployer = keypair_pubkey(Path::new(&wallet_path))?;
+    deployments::record_deployment(
+        "deploy",
+        cluster_url,
+        &deploy_output.program_id,
+        &deployer,
+        Some(&so_path),
+        deploy_output.signature,
+    )?;
+
+    Ok(())
+}
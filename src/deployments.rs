@@ -0,0 +1,348 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cluster::cluster_name;
+use crate::project::Project;
+
+const DEPLOYMENTS_DIR: &str = "deployments";
+
+/// a single entry of `deployments/<cluster>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deployment {
+    pub kind: String,
+    pub program_id: String,
+    /// `None` when the deployed binary isn't known, e.g. upgrades from an existing buffer
+    pub so_sha256: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_dirty: bool,
+    pub timestamp: String,
+    pub deployer: String,
+    pub signature: Option<String>,
+    pub cluster_url: String,
+}
+
+/// the build state a deployment is compared against
+struct BuildInfo {
+    label: String,
+    program_id: Option<String>,
+    so_sha256: Option<String>,
+    git_commit: Option<String>,
+    git_dirty: bool,
+}
+
+impl BuildInfo {
+    fn from_deployment(label: String, deployment: &Deployment) -> Self {
+        BuildInfo {
+            label,
+            program_id: Some(deployment.program_id.clone()),
+            so_sha256: deployment.so_sha256.clone(),
+            git_commit: deployment.git_commit.clone(),
+            git_dirty: deployment.git_dirty,
+        }
+    }
+}
+
+/// appends a deployment to `deployments/<cluster>.json`. `so_path` is the deployed
+/// binary, `None` when it isn't known locally.
+pub fn record_deployment(
+    kind: &str,
+    cluster_url: &str,
+    program_id: &str,
+    deployer: &str,
+    so_path: Option<&Path>,
+    signature: Option<String>,
+) -> Result<()> {
+    let (git_commit, git_dirty) = git_state();
+
+    let deployment = Deployment {
+        kind: kind.to_string(),
+        program_id: program_id.to_string(),
+        so_sha256: so_path.map(sha256_file).transpose()?,
+        git_commit,
+        git_dirty,
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        deployer: deployer.to_string(),
+        signature,
+        cluster_url: cluster_url.to_string(),
+    };
+
+    let ledger_path = ledger_path(&cluster_name(cluster_url));
+    let mut deployments = read_ledger(&ledger_path)?;
+    deployments.push(deployment);
+
+    fs::create_dir_all(DEPLOYMENTS_DIR)
+        .with_context(|| format!("Failed to create {} directory", DEPLOYMENTS_DIR))?;
+    fs::write(
+        &ledger_path,
+        serde_json::to_string_pretty(&deployments)? + "\n",
+    )
+    .with_context(|| format!("Failed to write {}", ledger_path.display()))?;
+
+    status!("📒 Recorded deployment in {}", ledger_path.display());
+
+    Ok(())
+}
+
+pub fn list_deployments(cluster: Option<&str>) -> Result<()> {
+    let clusters = match cluster {
+        Some(cluster) => vec![cluster_name(cluster)],
+        None => recorded_clusters()?,
+    };
+
+    if clusters.is_empty() {
+        status!("❌ No deployments recorded yet.");
+        status!("💡 Deployments are recorded by 'pinoc deploy' and 'pinoc upgrade'");
+        return Ok(());
+    }
+
+    for cluster in clusters {
+        let deployments = read_ledger(&ledger_path(&cluster))?;
+
+        out!("\n📒 Deployments on {}:", cluster);
+        if deployments.is_empty() {
+            out!("   No deployments recorded.");
+            continue;
+        }
+
+        out!(
+            "{:<4} {:<21} {:<8} {:<12} {:<14} Program ID",
+            "#",
+            "Timestamp",
+            "Kind",
+            "Commit",
+            "SHA-256"
+        );
+        out!(
+            "{:-<4} {:-<21} {:-<8} {:-<12} {:-<14} {:-<44}",
+            "",
+            "",
+            "",
+            "",
+            "",
+            ""
+        );
+
+        for (index, deployment) in deployments.iter().enumerate() {
+            out!(
+                "{:<4} {:<21} {:<8} {:<12} {:<14} {}",
+                index + 1,
+                deployment.timestamp,
+                deployment.kind,
+                format_commit(&deployment.git_commit, deployment.git_dirty),
+                deployment
+                    .so_sha256
+                    .as_deref()
+                    .map_or("unknown", |sha256| short(sha256, 12)),
+                deployment.program_id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// compares two deployments, or a deployment with the local build
+///
+/// references are `<cluster>` for the latest deployment, `<cluster>@<n>` for the
+/// n-th entry of the ledger and `local` for the current build
+pub fn diff_deployments(from: &str, to: Option<&str>) -> Result<()> {
+    let from = resolve_reference(from)?;
+    let to = resolve_reference(to.unwrap_or("local"))?;
+
+    status!("🔍 Comparing {} → {}\n", from.label, to.label);
+
+    out!("{:<12} {:<66} {}", "", from.label, to.label);
+    print_row(
+        "Program ID",
+        from.program_id.as_deref().unwrap_or("-"),
+        to.program_id.as_deref().unwrap_or("-"),
+    );
+    print_row(
+        "SHA-256",
+        from.so_sha256.as_deref().unwrap_or("unknown"),
+        to.so_sha256.as_deref().unwrap_or("unknown"),
+    );
+    print_row(
+        "Commit",
+        &format_commit(&from.git_commit, from.git_dirty),
+        &format_commit(&to.git_commit, to.git_dirty),
+    );
+    out!();
+
+    if from.so_sha256.is_none() || to.so_sha256.is_none() {
+        out!("⚠️  Can't compare program binaries: a deployment from an existing buffer has no recorded SHA-256");
+    } else if from.so_sha256 == to.so_sha256 {
+        out!("✅ Same program binary");
+    } else {
+        out!("⚠️  Program binaries differ");
+    }
+
+    if let (Some(from_commit), Some(to_commit)) = (&from.git_commit, &to.git_commit) {
+        if from_commit != to_commit {
+            let output = Command::new("git")
+                .arg("log")
+                .arg("--oneline")
+                .arg(format!("{}..{}", from_commit, to_commit))
+                .output();
+
+            if let Ok(output) = output {
+                if output.status.success() {
+                    let log = String::from_utf8_lossy(&output.stdout);
+                    out!("\n📝 Commits between {} and {}:", from.label, to.label);
+                    if log.trim().is_empty() {
+                        out!("   (none, {} is not ahead of {})", to.label, from.label);
+                    }
+                    for line in log.lines() {
+                        out!("   {}", line);
+                    }
+                }
+            }
+        }
+    }
+
+    if from.git_dirty || to.git_dirty {
+        out!("\n⚠️  Dirty builds include uncommitted changes that are not shown above.");
+    }
+
+    Ok(())
+}
+
+fn resolve_reference(reference: &str) -> Result<BuildInfo> {
+    if reference == "local" {
+        let so_path = Project::open(".")?.find_program_so()?;
+        let (git_commit, git_dirty) = git_state();
+        return Ok(BuildInfo {
+            label: "local".to_string(),
+            program_id: None,
+            so_sha256: Some(sha256_file(&so_path)?),
+            git_commit,
+            git_dirty,
+        });
+    }
+
+    let (cluster, index) = match reference.split_once('@') {
+        Some((cluster, index)) => {
+            let index: usize = index
+                .parse()
+                .with_context(|| format!("Invalid deployment number in '{}'", reference))?;
+            (cluster, Some(index))
+        }
+        None => (reference, None),
+    };
+
+    let cluster = cluster_name(cluster);
+    let deployments = read_ledger(&ledger_path(&cluster))?;
+    if deployments.is_empty() {
+        anyhow::bail!("No deployments recorded for {}", cluster);
+    }
+
+    let index = index.unwrap_or(deployments.len());
+    let deployment = index
+        .checked_sub(1)
+        .and_then(|i| deployments.get(i))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Deployment #{} not found for {} ({} recorded)",
+                index,
+                cluster,
+                deployments.len()
+            )
+        })?;
+
+    Ok(BuildInfo::from_deployment(
+        format!("{}@{}", cluster, index),
+        deployment,
+    ))
+}
+
+fn print_row(label: &str, from: &str, to: &str) {
+    let marker = if from == to { " " } else { "≠" };
+    out!("{:<11}{} {:<66} {}", label, marker, from, to);
+}
+
+fn format_commit(commit: &Option<String>, dirty: bool) -> String {
+    match commit {
+        Some(commit) if dirty => format!("{}-dirty", short(commit, 7)),
+        Some(commit) => short(commit, 7).to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn short(value: &str, len: usize) -> &str {
+    &value[..value.len().min(len)]
+}
+
+fn ledger_path(cluster: &str) -> PathBuf {
+    Path::new(DEPLOYMENTS_DIR).join(format!("{}.json", cluster))
+}
+
+fn read_ledger(path: &Path) -> Result<Vec<Deployment>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn recorded_clusters() -> Result<Vec<String>> {
+    let dir = Path::new(DEPLOYMENTS_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut clusters = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                clusters.push(stem.to_string());
+            }
+        }
+    }
+    clusters.sort();
+
+    Ok(clusters)
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(sha256_hex(&bytes))
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// returns the current commit and whether the work tree has uncommitted changes,
+/// ignoring the ledger itself
+pub fn git_state() -> (Option<String>, bool) {
+    let commit = Command::new("git")
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    let dirty = commit.is_some()
+        && Command::new("git")
+            .arg("status")
+            .arg("--porcelain")
+            .arg("--")
+            .arg(".")
+            .arg(format!(":(exclude){}", DEPLOYMENTS_DIR))
+            .output()
+            .map(|output| !output.stdout.is_empty())
+            .unwrap_or(false);
+
+    (commit, dirty)
+}
@@ -0,0 +1,316 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{self, expand_tilde};
+use crate::keys::{extract_current_program_id, keypair_pubkey};
+use crate::output;
+use crate::preflight::check_wallet;
+use crate::project::Project;
+use crate::verify::installed_toolchain;
+
+const SOLANA_INSTALL_HINT: &str =
+    "Install the Solana CLI: sh -c \"$(curl -sSfL https://release.anza.xyz/stable/install)\"";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Check {
+            name,
+            status: Status::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check {
+            name,
+            status: Status::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check {
+            name,
+            status: Status::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    checks: Vec<Check>,
+    passed: usize,
+    warnings: usize,
+    failed: usize,
+}
+
+/// checks the tools pinoc shells out to and, inside a project, its configuration
+pub fn run_doctor() -> Result<()> {
+    let mut checks = vec![
+        tool_check(
+            "cargo",
+            "cargo",
+            &["--version"],
+            "Install Rust from https://rustup.rs",
+        ),
+        build_sbf_check(),
+        platform_tools_check(),
+        tool_check("solana", "solana", &["--version"], SOLANA_INSTALL_HINT),
+        tool_check(
+            "solana-keygen",
+            "solana-keygen",
+            &["--version"],
+            SOLANA_INSTALL_HINT,
+        ),
+        git_check(),
+        default_wallet_check(),
+    ];
+
+    if Path::new("Cargo.toml").exists() || Path::new("Pinoc.toml").exists() {
+        checks.push(pinoc_toml_check());
+        checks.push(program_id_check());
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let report = Report {
+        passed: count(Status::Pass),
+        warnings: count(Status::Warn),
+        failed: count(Status::Fail),
+        checks,
+    };
+
+    output::event("doctor", serde_json::to_value(&report)?);
+    print_report(&report);
+
+    if report.failed > 0 {
+        anyhow::bail!("{} check(s) failed", report.failed);
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &Report) {
+    status!("🩺 Checking your pinoc environment\n");
+
+    for check in &report.checks {
+        let icon = match check.status {
+            Status::Pass => "✅",
+            Status::Warn => "⚠️ ",
+            Status::Fail => "❌",
+        };
+        out!("{} {:<16} {}", icon, check.name, check.message);
+        if let Some(hint) = &check.hint {
+            out!("   💡 {}", hint);
+        }
+    }
+
+    out!(
+        "\n{} passed, {} warning(s), {} failed",
+        report.passed,
+        report.warnings,
+        report.failed
+    );
+}
+
+/// first line of `<program> <args>` output, `None` when the program can't be run
+fn tool_version(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+fn tool_check(name: &'static str, program: &str, args: &[&str], hint: &str) -> Check {
+    match tool_version(program, args) {
+        Some(version) => Check::pass(name, version),
+        None => Check::fail(name, format!("'{}' not found", program), hint),
+    }
+}
+
+fn build_sbf_check() -> Check {
+    match installed_toolchain() {
+        Ok(toolchain) => Check::pass("cargo build-sbf", toolchain.build_sbf_version),
+        Err(_) => Check::fail(
+            "cargo build-sbf",
+            "'cargo build-sbf' not found",
+            SOLANA_INSTALL_HINT,
+        ),
+    }
+}
+
+/// cargo build-sbf downloads platform-tools into ~/.cache/solana/<version> on first use
+fn platform_tools_check() -> Check {
+    let Some(version) = installed_toolchain()
+        .ok()
+        .and_then(|toolchain| toolchain.platform_tools_version)
+    else {
+        return Check::warn(
+            "platform-tools",
+            "Could not determine the platform-tools version",
+            "Check 'cargo build-sbf --version'",
+        );
+    };
+
+    let installed = dirs::home_dir()
+        .map(|home| {
+            home.join(".cache")
+                .join("solana")
+                .join(&version)
+                .join("platform-tools")
+        })
+        .is_some_and(|dir| dir.exists());
+
+    if installed {
+        Check::pass("platform-tools", version)
+    } else {
+        Check::warn(
+            "platform-tools",
+            format!("{} is not installed yet", version),
+            "It is downloaded by the first 'pinoc build', or run 'cargo build-sbf --force-tools-install'",
+        )
+    }
+}
+
+fn git_check() -> Check {
+    match tool_version("git", &["--version"]) {
+        Some(version) => Check::pass("git", version),
+        None => Check::warn(
+            "git",
+            "'git' not found",
+            "Install git to version projects and record commits in the deployment ledger",
+        ),
+    }
+}
+
+fn default_wallet_check() -> Check {
+    match tool_version("solana", &["address"]) {
+        Some(address) => Check::pass("default wallet", address),
+        None => Check::fail(
+            "default wallet",
+            "No default wallet configured",
+            "Create one with: solana-keygen new",
+        ),
+    }
+}
+
+fn pinoc_toml_check() -> Check {
+    let config = match config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(error) => {
+            return Check::fail(
+                "Pinoc.toml",
+                format!("{:#}", error),
+                "Run 'pinoc init .' to create it, or fix the [provider] section",
+            )
+        }
+    };
+
+    let wallet = match expand_tilde(&config.provider.wallet) {
+        Ok(wallet) => wallet,
+        Err(error) => {
+            return Check::fail(
+                "Pinoc.toml",
+                error.to_string(),
+                "Use an absolute wallet path",
+            )
+        }
+    };
+
+    match check_wallet(&wallet) {
+        Ok(address) => Check::pass(
+            "Pinoc.toml",
+            format!("cluster {}, wallet {}", config.provider.cluster, address),
+        ),
+        Err(error) => Check::fail(
+            "Pinoc.toml",
+            error.to_string().lines().next().unwrap_or_default().to_string(),
+            format!(
+                "Create the wallet with 'solana-keygen new -o {}' or point [provider] wallet to an existing keypair",
+                config.provider.wallet
+            ),
+        ),
+    }
+}
+
+fn program_id_check() -> Check {
+    let project = match Project::open(".") {
+        Ok(project) => project,
+        Err(error) => {
+            return Check::fail(
+                "program id",
+                error.to_string(),
+                "Run this from the program crate",
+            )
+        }
+    };
+
+    let keypair_path = project.keypair_path();
+    if !keypair_path.exists() {
+        return Check::warn(
+            "program id",
+            format!("{} not found", keypair_path.display()),
+            "Run 'pinoc build' to generate it, then 'pinoc keys sync'",
+        );
+    }
+
+    let keypair_id = match keypair_pubkey(&keypair_path) {
+        Ok(id) => id,
+        Err(error) => {
+            return Check::fail(
+                "program id",
+                error.to_string(),
+                "Check that the Solana CLI is installed",
+            )
+        }
+    };
+
+    let declared_id = fs::read_to_string(project.lib_path())
+        .ok()
+        .and_then(|content| extract_current_program_id(&content));
+
+    match declared_id {
+        Some(declared_id) if declared_id == keypair_id => Check::pass("program id", keypair_id),
+        Some(declared_id) => Check::fail(
+            "program id",
+            format!(
+                "declare_id! is {} but the keypair is {}",
+                declared_id, keypair_id
+            ),
+            "Run 'pinoc keys sync'",
+        ),
+        None => Check::warn(
+            "program id",
+            format!("No declare_id! found in {}", project.lib_path().display()),
+            "Run 'pinoc keys sync' to see how to add it",
+        ),
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+use crate::compat::{self, Dependency, Release};
+use crate::error::PinocError;
+use crate::output;
+
+/// how a third-party crate has to be added to stay usable in a `no_std` program
+pub struct KnownCrate {
+    pub name: &'static str,
+    /// add with `default-features = false` because the defaults enable `std`
+    pub no_default_features: bool,
+    pub features: &'static [&'static str],
+    /// why the crate doesn't fit a `no_std`/`no_allocator!()` program, even when configured
+    pub warning: Option<&'static str>,
+}
+
+const fn known(
+    name: &'static str,
+    no_default_features: bool,
+    features: &'static [&'static str],
+    warning: Option<&'static str>,
+) -> KnownCrate {
+    KnownCrate {
+        name,
+        no_default_features,
+        features,
+        warning,
+    }
+}
+
+/// crates commonly pulled into pinocchio programs
+pub const KNOWN_CRATES: &[KnownCrate] = &[
+    known("bytemuck", false, &["derive"], None),
+    known("heapless", false, &[], None),
+    known("arrayvec", true, &[], None),
+    known("thiserror", true, &[], None),
+    known("serde", true, &["derive"], None),
+    known(
+        "borsh",
+        true,
+        &["derive"],
+        Some("borsh allocates for Vec, String and Box, which fails under no_allocator!()"),
+    ),
+    known(
+        "serde_json",
+        true,
+        &["alloc"],
+        Some("serde_json needs an allocator, which fails under no_allocator!()"),
+    ),
+    known(
+        "solana-program",
+        false,
+        &[],
+        Some("solana-program links std and its own allocator; use pinocchio and its companion crates instead"),
+    ),
+    known(
+        "anchor-lang",
+        false,
+        &[],
+        Some("anchor-lang depends on solana-program and std; it can't be used in a pinocchio program"),
+    ),
+    known(
+        "spl-token",
+        false,
+        &[],
+        Some("spl-token depends on solana-program and std; use pinocchio-token instead"),
+    ),
+    known(
+        "spl-associated-token-account",
+        false,
+        &[],
+        Some("spl-associated-token-account depends on solana-program and std; use pinocchio-associated-token-account instead"),
+    ),
+    known(
+        "solana-sdk",
+        false,
+        &[],
+        Some("solana-sdk is std-only; add it with --dev to use it in tests"),
+    ),
+];
+
+pub fn find_known_crate(name: &str) -> Option<&'static KnownCrate> {
+    KNOWN_CRATES.iter().find(|known| known.name == name)
+}
+
+/// `pinoc add`: adds a crate with a version and features that fit the project's pinocchio
+pub fn add_package(spec: &str, dev: bool) -> Result<()> {
+    if !Path::new("Cargo.toml").exists() {
+        anyhow::bail!(
+            "Cargo.toml not found. Please run this command from the project root directory."
+        );
+    }
+
+    let (name, version) = match spec.split_once('@') {
+        Some((name, version)) => (name, Some(version.to_string())),
+        None => (spec, None),
+    };
+
+    let release = compat::project_release(Path::new("."));
+    let mut add_cmd = Command::new("cargo");
+    add_cmd.arg("add");
+    if dev {
+        add_cmd.arg("--dev");
+    }
+
+    let companion = companion(name, dev, release);
+    let version = version.or_else(|| companion.map(|(version, _)| version.to_string()));
+    let known = find_known_crate(name);
+
+    match &version {
+        Some(version) => {
+            add_cmd.arg(format!("{}@{}", name, version));
+        }
+        None => {
+            add_cmd.arg(name);
+        }
+    }
+
+    if let Some((_, features)) = companion.filter(|(_, features)| !features.is_empty()) {
+        add_cmd.arg("--features").arg(features.join(","));
+    }
+
+    // tests run with std, so dev-dependencies keep their defaults
+    if let Some(known) = known.filter(|_| !dev) {
+        if known.no_default_features {
+            add_cmd.arg("--no-default-features");
+        }
+        if !known.features.is_empty() {
+            add_cmd.arg("--features").arg(known.features.join(","));
+        }
+    }
+
+    status!("📦 Adding package: {}", name);
+    if let (Some((version, _)), Some(release)) = (companion, release) {
+        status!(
+            "📌 Using {} {} (compatible with pinocchio {})",
+            name,
+            version,
+            release.pinocchio
+        );
+    }
+
+    output::command(&add_cmd);
+    let status = add_cmd
+        .stdout(output::child_stdout())
+        .status()
+        .map_err(PinocError::tool("cargo"))?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to add package '{}' with exit code: {:?}",
+            name,
+            status.code()
+        );
+    }
+
+    status!("✅ Package '{}' added successfully!", name);
+    if let Some(warning) = known.filter(|_| !dev).and_then(|known| known.warning) {
+        warning!("⚠️  {}", warning);
+    }
+
+    Ok(())
+}
+
+/// the version and features of a pinocchio companion or test crate matching the project's
+/// release (the latest known one when the project doesn't depend on pinocchio yet)
+fn companion(
+    name: &str,
+    dev: bool,
+    release: Option<&'static Release>,
+) -> Option<(&'static str, &'static [&'static str])> {
+    let release = release.unwrap_or_else(compat::latest_release);
+    if name == "pinocchio" {
+        return Some((release.pinocchio, &[]));
+    }
+
+    let find = |dependencies: &'static [Dependency]| {
+        dependencies
+            .iter()
+            .find(|dependency| dependency.name == name)
+    };
+    // companion crates added with --dev still have to match the project's pinocchio
+    let dependency = if dev {
+        find(release.dev_dependencies).or_else(|| find(release.dependencies))
+    } else {
+        find(release.dependencies)
+    };
+    dependency.map(|dependency| (dependency.version, dependency.features))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pins_companion_crates_to_the_release() {
+        let release = compat::find_release("0.8").unwrap();
+        assert_eq!(
+            companion("pinocchio-system", false, Some(release)).map(|(version, _)| version),
+            Some("0.2.3")
+        );
+        assert_eq!(
+            companion("pinocchio", false, Some(release)).map(|(version, _)| version),
+            Some(release.pinocchio)
+        );
+        assert_eq!(companion("serde", false, Some(release)), None);
+    }
+
+    #[test]
+    fn pins_dev_companion_crates_to_the_release() {
+        let release = compat::find_release("0.8").unwrap();
+        assert_eq!(
+            companion("pinocchio-token", true, Some(release)).map(|(version, _)| version),
+            Some("0.3.0")
+        );
+        assert_eq!(
+            companion("mollusk-svm", true, Some(release)).map(|(version, _)| version),
+            Some("0.3.0")
+        );
+        assert_eq!(companion("mollusk-svm", false, Some(release)), None);
+    }
+}
@@ -0,0 +1,194 @@
+use serde::Serialize;
+use std::io;
+use std::path::PathBuf;
+
+/// errors returned by the library API and reported by the pinoc binary
+///
+/// Each variant has a stable `code` and a process exit code, so scripts can tell failures apart:
+///
+/// | exit code | code                   | meaning                                          |
+/// | --------- | ---------------------- | ------------------------------------------------ |
+/// | 1         | `error`                | any other failure                                |
+/// | 2         | `usage`                | invalid command line arguments (reported by clap) |
+/// | 3         | `tool_missing`         | cargo, solana, solana-keygen or git can't be run |
+/// | 4         | `not_a_project`        | no Cargo.toml or Pinoc.toml in the project       |
+/// | 5         | `config_invalid`       | Pinoc.toml, Cargo.toml or the wallet is invalid  |
+/// | 6         | `invalid_project_name` | the project name can't be used as a crate name   |
+/// | 7         | `keypair_not_found`    | the program keypair hasn't been generated        |
+/// | 8         | `keypair_mismatch`     | `declare_id!` doesn't match the program keypair  |
+/// | 9         | `build_failed`         | `cargo build-sbf` failed                         |
+/// | 10        | `test_failed`          | `cargo test` failed                              |
+/// | 11        | `command_failed`       | another external command failed                  |
+/// | 12        | `io`                   | a file couldn't be read or written               |
+#[derive(Debug, thiserror::Error)]
+pub enum PinocError {
+    #[error("{file} not found in {}. Please run this command inside a project.", dir.display())]
+    NotAProject { dir: PathBuf, file: &'static str },
+
+    #[error("Invalid project name '{name}'. {reason}")]
+    InvalidProjectName { name: String, reason: String },
+
+    #[error("Failed to parse {}: {message}", path.display())]
+    InvalidConfig { path: PathBuf, message: String },
+
+    #[error("Failed to run '{program}'. Make sure it is installed and on your PATH")]
+    ToolMissing {
+        program: &'static str,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("'{command}' failed: {message}")]
+    CommandFailed { command: String, message: String },
+
+    #[error("{reason}")]
+    InvalidWallet { path: String, reason: String },
+
+    #[error("Keypair file not found: {}. Run 'pinoc build' first.", path.display())]
+    KeypairNotFound { path: PathBuf },
+
+    #[error("Program ID mismatch: declare_id! is {declared} but the program keypair is {keypair}")]
+    KeypairMismatch { declared: String, keypair: String },
+
+    #[error("Build failed: {message}")]
+    BuildFailed { message: String },
+
+    #[error("Tests failed: {message}")]
+    TestFailed { message: String },
+
+    #[error("Failed to access {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+pub type Result<T, E = PinocError> = std::result::Result<T, E>;
+
+/// how the binary reports an error with `--json`
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    pub code: &'static str,
+    pub exit_code: i32,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl PinocError {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> PinocError {
+        let path = path.into();
+        move |source| PinocError::Io { path, source }
+    }
+
+    /// maps the error of spawning `program` to `ToolMissing`
+    pub(crate) fn tool(program: &'static str) -> impl FnOnce(io::Error) -> PinocError {
+        move |source| PinocError::ToolMissing { program, source }
+    }
+
+    /// a stable identifier for scripts
+    pub fn code(&self) -> &'static str {
+        match self {
+            PinocError::ToolMissing { .. } => "tool_missing",
+            PinocError::NotAProject { .. } => "not_a_project",
+            PinocError::InvalidConfig { .. } | PinocError::InvalidWallet { .. } => "config_invalid",
+            PinocError::InvalidProjectName { .. } => "invalid_project_name",
+            PinocError::KeypairNotFound { .. } => "keypair_not_found",
+            PinocError::KeypairMismatch { .. } => "keypair_mismatch",
+            PinocError::BuildFailed { .. } => "build_failed",
+            PinocError::TestFailed { .. } => "test_failed",
+            PinocError::CommandFailed { .. } => "command_failed",
+            PinocError::Io { .. } => "io",
+            PinocError::Other(_) => "error",
+        }
+    }
+
+    /// the exit code of the pinoc binary for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PinocError::ToolMissing { .. } => 3,
+            PinocError::NotAProject { .. } => 4,
+            PinocError::InvalidConfig { .. } | PinocError::InvalidWallet { .. } => 5,
+            PinocError::InvalidProjectName { .. } => 6,
+            PinocError::KeypairNotFound { .. } => 7,
+            PinocError::KeypairMismatch { .. } => 8,
+            PinocError::BuildFailed { .. } => 9,
+            PinocError::TestFailed { .. } => 10,
+            PinocError::CommandFailed { .. } => 11,
+            PinocError::Io { .. } => 12,
+            PinocError::Other(_) => 1,
+        }
+    }
+
+    /// what to do about the error, when pinoc knows
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            PinocError::ToolMissing { program: "cargo", .. } => {
+                "Install Rust from https://rustup.rs".to_string()
+            }
+            PinocError::ToolMissing { program: "git", .. } => {
+                "Install git, or pass --no-git to pinoc init".to_string()
+            }
+            PinocError::ToolMissing { .. } => {
+                "Install the Solana CLI: sh -c \"$(curl -sSfL https://release.anza.xyz/stable/install)\""
+                    .to_string()
+            }
+            PinocError::NotAProject { .. } => {
+                "Run 'pinoc init <name>' to create a project, or 'pinoc init .' to adopt this crate"
+                    .to_string()
+            }
+            PinocError::InvalidConfig { path, .. } => {
+                format!("Fix {} and run 'pinoc doctor' to check it", path.display())
+            }
+            PinocError::InvalidWallet { path, .. } => format!(
+                "Create one with 'solana-keygen new -o {}', or point Pinoc.toml / --wallet to an existing keypair",
+                path
+            ),
+            PinocError::InvalidProjectName { .. } => {
+                "Use a name like 'my_program' or 'my-program'".to_string()
+            }
+            PinocError::KeypairNotFound { .. } => {
+                "Run 'pinoc build' to generate it, then 'pinoc keys sync'".to_string()
+            }
+            PinocError::KeypairMismatch { .. } => {
+                "Run 'pinoc keys sync' and 'pinoc build'".to_string()
+            }
+            PinocError::BuildFailed { .. } => {
+                "Fix the errors reported above and run 'pinoc build' again".to_string()
+            }
+            PinocError::TestFailed { .. }
+            | PinocError::CommandFailed { .. }
+            | PinocError::Io { .. }
+            | PinocError::Other(_) => return None,
+        };
+        Some(hint)
+    }
+
+    /// finds the `PinocError` behind an error returned by a command, looking through context
+    /// and `Other` wrappers
+    pub fn find(error: &anyhow::Error) -> Option<&PinocError> {
+        let mut found = error.downcast_ref::<PinocError>()?;
+        while let PinocError::Other(inner) = found {
+            match inner.downcast_ref::<PinocError>() {
+                Some(next) => found = next,
+                None => break,
+            }
+        }
+        Some(found)
+    }
+
+    /// the report the binary prints for an error returned by a command
+    pub fn report(error: &anyhow::Error) -> ErrorReport {
+        let typed = PinocError::find(error);
+        ErrorReport {
+            code: typed.map_or("error", PinocError::code),
+            exit_code: typed.map_or(1, PinocError::exit_code),
+            message: format!("{:#}", error),
+            hint: typed.and_then(PinocError::hint),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{PinocError, Result};
+use crate::project::Project;
+use crate::upgrade::BUFFER_KEYPAIR_SUFFIX;
+
+/// a program keypair in the deploy directory
+#[derive(Debug, Clone)]
+pub struct ProgramKey {
+    pub name: String,
+    pub pubkey: String,
+    pub path: PathBuf,
+}
+
+/// what `sync` did to the `declare_id!` in the program source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// `declare_id!` already matches the program keypair
+    Unchanged { program_id: String },
+    Updated {
+        previous: String,
+        program_id: String,
+    },
+    /// the source has no `declare_id!` to update
+    MissingDeclareId { program_id: String },
+}
+
+/// lists the program keypairs of the project, skipping buffer keypairs
+pub fn list(project: &Project) -> Result<Vec<ProgramKey>> {
+    let deploy_dir = project.deploy_dir();
+    let mut keys = Vec::new();
+    if !deploy_dir.exists() {
+        return Ok(keys);
+    }
+
+    for entry in fs::read_dir(&deploy_dir).map_err(PinocError::io(&deploy_dir))? {
+        let path = entry.map_err(PinocError::io(&deploy_dir))?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        if file_name.ends_with("-keypair.json") && !file_name.ends_with(BUFFER_KEYPAIR_SUFFIX) {
+            // keypairs `solana address` can't read aren't program keypairs
+            if let Ok(pubkey) = keypair_pubkey(&path) {
+                keys.push(ProgramKey {
+                    name: file_name.trim_end_matches("-keypair.json").to_string(),
+                    pubkey,
+                    path: path.clone(),
+                });
+            }
+        }
+    }
+
+    keys.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(keys)
+}
+
+/// updates the `declare_id!` in the program source to the address of the program keypair
+pub fn sync(project: &Project) -> Result<SyncOutcome> {
+    let keypair_path = project.keypair_path();
+    if !keypair_path.exists() {
+        return Err(PinocError::KeypairNotFound { path: keypair_path });
+    }
+
+    let program_id = keypair_pubkey(&keypair_path)?;
+
+    let lib_path = project.lib_path();
+    let lib_content = fs::read_to_string(&lib_path).map_err(PinocError::io(&lib_path))?;
+
+    let Some(previous) = extract_current_program_id(&lib_content) else {
+        return Ok(SyncOutcome::MissingDeclareId { program_id });
+    };
+    if previous == program_id {
+        return Ok(SyncOutcome::Unchanged { program_id });
+    }
+
+    if let Some(updated_content) = update_declare_id(&lib_content, &program_id) {
+        fs::write(&lib_path, updated_content).map_err(PinocError::io(&lib_path))?;
+    }
+
+    Ok(SyncOutcome::Updated {
+        previous,
+        program_id,
+    })
+}
+
+/// returns the public key of a keypair file using `solana address`
+pub fn keypair_pubkey(keypair_path: &Path) -> Result<String> {
+    let address_output = Command::new("solana")
+        .arg("address")
+        .arg("-k")
+        .arg(keypair_path)
+        .output()
+        .map_err(PinocError::tool("solana"))?;
+
+    if !address_output.status.success() {
+        let error = String::from_utf8_lossy(&address_output.stderr);
+        return Err(PinocError::CommandFailed {
+            command: format!("solana address -k {}", keypair_path.display()),
+            message: error.trim().to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&address_output.stdout)
+        .trim()
+        .to_string())
+}
+
+/// replaces the address in the `declare_id!` macro, leaving the rest of the file untouched
+pub(crate) fn update_declare_id(lib_content: &str, new_pubkey: &str) -> Option<String> {
+    let start = lib_content.find("declare_id!(\"")? + "declare_id!(\"".len();
+    let end = start + lib_content[start..].find('"')?;
+
+    Some(format!(
+        "{}{}{}",
+        &lib_content[..start],
+        new_pubkey,
+        &lib_content[end..]
+    ))
+}
+
+pub(crate) fn extract_current_program_id(lib_content: &str) -> Option<String> {
+    for line in lib_content.lines() {
+        if line.contains("declare_id!") {
+            // Look for the pattern: declare_id!("...") or pinocchio_pubkey::declare_id!("...")
+            if let Some(start) = line.find("declare_id!(\"") {
+                let after_declare = &line[start + 13..]; // Skip "declare_id!(\""
+                if let Some(end) = after_declare.find("\"") {
+                    return Some(after_declare[..end].to_string());
+                }
+            }
+        }
+    }
+    None
+}
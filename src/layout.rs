@@ -0,0 +1,685 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// a field type pinoc can decode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Bool,
+    Unsigned(usize),
+    Signed(usize),
+    Pubkey,
+    Array(Box<FieldType>, usize),
+    /// another `#[repr(C)]` struct of the project
+    Struct(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub ty: FieldType,
+}
+
+/// a `#[repr(C)]` struct found in the program sources
+#[derive(Debug, Clone)]
+pub struct Layout {
+    pub name: String,
+    pub fields: Vec<Field>,
+    /// `#[repr(C, packed)]`: fields follow each other without padding
+    pub packed: bool,
+}
+
+/// the structs of a project, by name
+#[derive(Debug, Clone, Default)]
+pub struct Layouts {
+    pub structs: BTreeMap<String, Layout>,
+}
+
+impl Layouts {
+    /// the structs of `src/states/`, or the accounts of the shank IDL in `idl/` when the
+    /// project keeps its state elsewhere
+    pub fn load(root: &Path) -> Result<Self> {
+        let layouts = Layouts::from_sources(&root.join("src").join("states"))?;
+        if !layouts.is_empty() {
+            return Ok(layouts);
+        }
+        Layouts::from_idl(&root.join("idl"))
+    }
+
+    /// reads the `#[repr(C)]` structs of the `.rs` files in `dir`, recursively
+    pub fn from_sources(dir: &Path) -> Result<Self> {
+        let mut layouts = Layouts::default();
+        for source in read_sources(dir)? {
+            for layout in parse_structs(&source) {
+                layouts.structs.insert(layout.name.clone(), layout);
+            }
+        }
+        Ok(layouts)
+    }
+
+    /// reads the account types of the shank IDLs in `dir`
+    pub fn from_idl(dir: &Path) -> Result<Self> {
+        let mut layouts = Layouts::default();
+        if !dir.exists() {
+            return Ok(layouts);
+        }
+
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let idl: Idl = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+            for definition in idl.accounts.into_iter().chain(idl.types) {
+                let mut fields = Vec::new();
+                for field in definition.ty.fields {
+                    let Some(ty) = idl_type(&field.ty) else {
+                        // layouts with types pinoc can't size are skipped entirely
+                        fields.clear();
+                        break;
+                    };
+                    fields.push(Field {
+                        name: field.name,
+                        ty,
+                    });
+                }
+                if !fields.is_empty() {
+                    layouts.structs.insert(
+                        definition.name.clone(),
+                        Layout {
+                            name: definition.name,
+                            fields,
+                            packed: false,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(layouts)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Layout> {
+        self.structs.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.structs.is_empty()
+    }
+
+    /// the size of `ty` with `#[repr(C)]` padding, `None` for unknown structs
+    pub fn size_of(&self, ty: &FieldType) -> Option<usize> {
+        match ty {
+            FieldType::Bool => Some(1),
+            FieldType::Unsigned(size) | FieldType::Signed(size) => Some(*size),
+            FieldType::Pubkey => Some(32),
+            FieldType::Array(item, len) => Some(self.size_of(item)? * len),
+            FieldType::Struct(name) => {
+                let layout = self.get(name)?;
+                let mut offset = 0;
+                for field in &layout.fields {
+                    offset = align_up(offset, self.field_align(layout, &field.ty)?);
+                    offset += self.size_of(&field.ty)?;
+                }
+                Some(align_up(offset, self.align_of(ty)?))
+            }
+        }
+    }
+
+    fn align_of(&self, ty: &FieldType) -> Option<usize> {
+        match ty {
+            FieldType::Bool | FieldType::Pubkey => Some(1),
+            FieldType::Unsigned(size) | FieldType::Signed(size) => Some(*size),
+            FieldType::Array(item, _) => self.align_of(item),
+            FieldType::Struct(name) => {
+                let layout = self.get(name)?;
+                layout
+                    .fields
+                    .iter()
+                    .map(|field| self.field_align(layout, &field.ty))
+                    .try_fold(1, |align, field_align| Some(align.max(field_align?)))
+            }
+        }
+    }
+
+    /// the alignment of a field inside `layout`, which is 1 in packed structs
+    fn field_align(&self, layout: &Layout, ty: &FieldType) -> Option<usize> {
+        if layout.packed {
+            return Some(1);
+        }
+        self.align_of(ty)
+    }
+
+    /// the size of the struct `name`, like `DataLen::LEN`
+    pub fn len(&self, name: &str) -> Option<usize> {
+        self.size_of(&FieldType::Struct(name.to_string()))
+    }
+
+    /// the structs whose size is `len`
+    pub fn with_len(&self, len: usize) -> Vec<&Layout> {
+        self.structs
+            .values()
+            .filter(|layout| self.len(&layout.name) == Some(len))
+            .collect()
+    }
+
+    /// decodes `data` as the struct `name`, keeping the field order
+    pub fn decode(&self, name: &str, data: &[u8]) -> Result<Vec<(String, Value)>> {
+        let layout = self
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown type '{}'", name))?;
+        let len = self
+            .len(name)
+            .ok_or_else(|| anyhow::anyhow!("Can't compute the size of '{}'", name))?;
+        if data.len() < len {
+            anyhow::bail!(
+                "'{}' is {} bytes but the account has {} bytes of data",
+                name,
+                len,
+                data.len()
+            );
+        }
+
+        let mut fields = Vec::new();
+        for (offset, field) in self.offsets(layout) {
+            let size = self.size_of(&field.ty).unwrap_or(0);
+            fields.push((
+                field.name.clone(),
+                self.decode_value(&field.ty, &data[offset..offset + size]),
+            ));
+        }
+        Ok(fields)
+    }
+
+    /// the offset of each field of `layout`, with `#[repr(C)]` padding
+    pub fn offsets<'a>(&self, layout: &'a Layout) -> Vec<(usize, &'a Field)> {
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        for field in &layout.fields {
+            offset = align_up(offset, self.field_align(layout, &field.ty).unwrap_or(1));
+            offsets.push((offset, field));
+            offset += self.size_of(&field.ty).unwrap_or(0);
+        }
+        offsets
+    }
+
+    fn decode_value(&self, ty: &FieldType, bytes: &[u8]) -> Value {
+        match ty {
+            FieldType::Bool => Value::Bool(bytes[0] != 0),
+            FieldType::Unsigned(size) => {
+                let mut buf = [0u8; 16];
+                buf[..*size].copy_from_slice(bytes);
+                let value = u128::from_le_bytes(buf);
+                // JSON numbers lose precision past 2^53
+                match u64::try_from(value) {
+                    Ok(value) if value < 1 << 53 => json!(value),
+                    _ => json!(value.to_string()),
+                }
+            }
+            FieldType::Signed(size) => {
+                let fill = if bytes[size - 1] & 0x80 != 0 { 0xff } else { 0 };
+                let mut buf = [fill; 16];
+                buf[..*size].copy_from_slice(bytes);
+                let value = i128::from_le_bytes(buf);
+                match i64::try_from(value) {
+                    Ok(value) if value.unsigned_abs() < 1 << 53 => json!(value),
+                    _ => json!(value.to_string()),
+                }
+            }
+            FieldType::Pubkey => json!(bs58::encode(bytes).into_string()),
+            FieldType::Array(item, len) => {
+                let size = self.size_of(item).unwrap_or(0);
+                Value::Array(
+                    (0..*len)
+                        .map(|i| self.decode_value(item, &bytes[i * size..(i + 1) * size]))
+                        .collect(),
+                )
+            }
+            FieldType::Struct(name) => match self.decode(name, bytes) {
+                Ok(fields) => Value::Object(fields.into_iter().collect()),
+                Err(_) => Value::Null,
+            },
+        }
+    }
+}
+
+/// finds the `#[repr(C)]` structs with named fields in a source file
+pub fn parse_structs(source: &str) -> Vec<Layout> {
+    let source = strip_comments(source);
+    let mut layouts = Vec::new();
+
+    let mut rest = source.as_str();
+    while let Some(start) = rest.find("#[repr(C") {
+        rest = &rest[start..];
+        let Some(struct_start) = rest.find("struct ") else {
+            break;
+        };
+        // the attribute has to belong to this struct, not to an enum or fn further down
+        let attributes = &rest[..struct_start];
+        if attributes.contains(['{', ';']) {
+            rest = &rest[1..];
+            continue;
+        }
+        let packed = attributes.contains("packed");
+        let after = &rest[struct_start + "struct ".len()..];
+        let Some(body_start) = after.find(['{', ';', '(']) else {
+            break;
+        };
+        let name = after[..body_start].trim();
+        // `packed(2)` and up keep some padding, which isn't modelled
+        if after.as_bytes()[body_start] != b'{'
+            || name.contains('<')
+            || (attributes.contains("packed(") && !attributes.contains("packed(1)"))
+        {
+            rest = after;
+            continue;
+        }
+        let Some(body_end) = after[body_start..].find('}') else {
+            break;
+        };
+        let body = &after[body_start + 1..body_start + body_end];
+
+        let fields: Option<Vec<Field>> = split_fields(body)
+            .into_iter()
+            .map(|field| {
+                let (name, ty) = strip_visibility(field).split_once(':')?;
+                Some(Field {
+                    name: name.trim().to_string(),
+                    ty: parse_type(ty.trim())?,
+                })
+            })
+            .collect();
+        if let Some(fields) = fields {
+            layouts.push(Layout {
+                name: name.to_string(),
+                fields,
+                packed,
+            });
+        }
+        rest = &after[body_start + body_end..];
+    }
+
+    layouts
+}
+
+/// parses a Rust type; other structs are assumed to be project structs
+pub fn parse_type(ty: &str) -> Option<FieldType> {
+    let ty = ty.trim();
+    if let Some(array) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+        let (item, len) = array.rsplit_once(';')?;
+        return Some(FieldType::Array(
+            Box::new(parse_type(item)?),
+            len.trim().replace('_', "").parse().ok()?,
+        ));
+    }
+
+    let ty = ty.rsplit("::").next().unwrap_or(ty);
+    Some(match ty {
+        "bool" => FieldType::Bool,
+        "u8" => FieldType::Unsigned(1),
+        "u16" => FieldType::Unsigned(2),
+        "u32" => FieldType::Unsigned(4),
+        "u64" => FieldType::Unsigned(8),
+        "u128" => FieldType::Unsigned(16),
+        "i8" => FieldType::Signed(1),
+        "i16" => FieldType::Signed(2),
+        "i32" => FieldType::Signed(4),
+        "i64" => FieldType::Signed(8),
+        "i128" => FieldType::Signed(16),
+        "Pubkey" | "Address" => FieldType::Pubkey,
+        name if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+            FieldType::Struct(name.to_string())
+        }
+        _ => return None,
+    })
+}
+
+/// renders a decoded value for humans: pubkeys and numbers as is, byte arrays as hex
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(items)
+            if items
+                .iter()
+                .all(|item| item.as_u64().is_some_and(|b| b < 256)) =>
+        {
+            let hex: String = items
+                .iter()
+                .map(|item| format!("{:02x}", item.as_u64().unwrap_or(0)))
+                .collect();
+            format!("0x{}", hex)
+        }
+        value => value.to_string(),
+    }
+}
+
+/// `pub(in crate::states) owner: u8` -> `owner: u8`, leaving names like `pubkey` alone
+fn strip_visibility(field: &str) -> &str {
+    let field = field.trim();
+    let Some(rest) = field.strip_prefix("pub") else {
+        return field;
+    };
+    if rest.starts_with(char::is_whitespace) {
+        return rest.trim_start();
+    }
+    match rest.strip_prefix('(').and_then(|rest| rest.split_once(')')) {
+        Some((_, name)) => name.trim_start(),
+        None => field,
+    }
+}
+
+fn align_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// splits a list at the commas that aren't inside brackets, dropping attributes
+pub(crate) fn split_fields(body: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in body.char_indices() {
+        match c {
+            '[' | '(' | '{' | '<' => depth += 1,
+            ']' | ')' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                fields.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&body[start..]);
+
+    fields
+        .into_iter()
+        .map(|field| {
+            // drop attributes like #[allow(dead_code)] in front of the field
+            let mut field = field.trim();
+            while field.starts_with("#[") {
+                field = field.find(']').map_or("", |end| field[end + 1..].trim());
+            }
+            field
+        })
+        .filter(|field| !field.is_empty())
+        .collect()
+}
+
+/// the contents of the `.rs` files in `dir`, recursively, without comments
+pub(crate) fn read_sources(dir: &Path) -> Result<Vec<String>> {
+    let mut sources = Vec::new();
+    if !dir.exists() {
+        return Ok(sources);
+    }
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            sources.extend(read_sources(&path)?);
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("rs") {
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            sources.push(strip_comments(&source));
+        }
+    }
+    Ok(sources)
+}
+
+/// the text between the braces of the first block starting at `header`, e.g. `impl MyState {`
+pub(crate) fn block<'a>(source: &'a str, header: &str) -> Option<&'a str> {
+    let start = source.find(header)?;
+    let open = start + source[start..].find('{')? + 1;
+    let mut depth = 1;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[open..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+pub(crate) fn strip_comments(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| match line.find("//") {
+            Some(start) => &line[..start],
+            None => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// the parts of a shank IDL pinoc reads
+#[derive(Deserialize)]
+struct Idl {
+    #[serde(default)]
+    accounts: Vec<IdlTypeDefinition>,
+    #[serde(default)]
+    types: Vec<IdlTypeDefinition>,
+}
+
+#[derive(Deserialize)]
+struct IdlTypeDefinition {
+    name: String,
+    #[serde(rename = "type")]
+    ty: IdlStruct,
+}
+
+#[derive(Deserialize)]
+struct IdlStruct {
+    #[serde(default)]
+    fields: Vec<IdlField>,
+}
+
+#[derive(Deserialize)]
+struct IdlField {
+    name: String,
+    #[serde(rename = "type")]
+    ty: Value,
+}
+
+fn idl_type(ty: &Value) -> Option<FieldType> {
+    match ty {
+        Value::String(name) => match name.as_str() {
+            "publicKey" | "pubkey" => Some(FieldType::Pubkey),
+            name => parse_type(name).filter(|ty| !matches!(ty, FieldType::Struct(_))),
+        },
+        Value::Object(object) => {
+            if let Some(array) = object.get("array").and_then(Value::as_array) {
+                let item = idl_type(array.first()?)?;
+                let len = array.get(1)?.as_u64()? as usize;
+                return Some(FieldType::Array(Box::new(item), len));
+            }
+            match object.get("defined")? {
+                Value::String(name) => Some(FieldType::Struct(name.clone())),
+                Value::Object(defined) => Some(FieldType::Struct(
+                    defined.get("name")?.as_str()?.to_string(),
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layouts(source: &str) -> Layouts {
+        Layouts {
+            structs: parse_structs(source)
+                .into_iter()
+                .map(|layout| (layout.name.clone(), layout))
+                .collect(),
+        }
+    }
+
+    fn offsets(layouts: &Layouts, name: &str) -> Vec<(usize, String)> {
+        layouts
+            .offsets(layouts.get(name).unwrap())
+            .into_iter()
+            .map(|(offset, field)| (offset, field.name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn repr_c_offsets_include_padding() {
+        let layouts = layouts(
+            r#"
+            #[repr(C)]
+            pub struct Vault {
+                pub flag: bool,
+                pub amount: u64,
+                pub owner: [u8; 32],
+                pub small: u16,
+            }
+            "#,
+        );
+
+        assert_eq!(
+            offsets(&layouts, "Vault"),
+            [
+                (0, "flag".to_string()),
+                (8, "amount".to_string()),
+                (16, "owner".to_string()),
+                (48, "small".to_string()),
+            ]
+        );
+        // trailing padding up to the u64 alignment
+        assert_eq!(layouts.len("Vault"), Some(56));
+    }
+
+    #[test]
+    fn nested_structs_use_their_alignment() {
+        let layouts = layouts(
+            r#"
+            #[repr(C)]
+            pub struct Inner { a: u8, b: u32 }
+
+            #[repr(C)]
+            pub struct Outer { tag: u8, inner: Inner, items: [Inner; 2] }
+            "#,
+        );
+
+        assert_eq!(layouts.len("Inner"), Some(8));
+        assert_eq!(
+            offsets(&layouts, "Outer"),
+            [
+                (0, "tag".to_string()),
+                (4, "inner".to_string()),
+                (12, "items".to_string()),
+            ]
+        );
+        assert_eq!(layouts.len("Outer"), Some(28));
+    }
+
+    #[test]
+    fn packed_structs_have_no_padding() {
+        let layouts = layouts(
+            r#"
+            #[repr(C, packed)]
+            pub struct Packed { flag: bool, amount: u64, small: u16 }
+
+            #[repr(C, packed(4))]
+            pub struct Aligned { flag: bool, amount: u64 }
+            "#,
+        );
+
+        assert_eq!(
+            offsets(&layouts, "Packed"),
+            [
+                (0, "flag".to_string()),
+                (1, "amount".to_string()),
+                (9, "small".to_string()),
+            ]
+        );
+        assert_eq!(layouts.len("Packed"), Some(11));
+        assert!(layouts.get("Aligned").is_none());
+    }
+
+    #[test]
+    fn visibility_is_stripped_from_field_names() {
+        let layouts = layouts(
+            r#"
+            #[repr(C)]
+            pub struct Names {
+                pubkey: Pubkey,
+                publisher: u8,
+                pub owner: Pubkey,
+                pub(crate) a: u8,
+                pub(super) b: u8,
+                pub(in crate::states) c: u8,
+            }
+            "#,
+        );
+
+        let names: Vec<&str> = layouts
+            .get("Names")
+            .unwrap()
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, ["pubkey", "publisher", "owner", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn only_named_repr_c_structs_are_parsed() {
+        let layouts = layouts(
+            r#"
+            #[repr(C)]
+            pub enum Kind { A, B }
+
+            pub struct Plain { a: u8 }
+
+            #[repr(C)]
+            pub struct Tuple(u8, u8);
+
+            #[repr(C)]
+            pub struct State { kind: u8 }
+            "#,
+        );
+
+        assert_eq!(layouts.structs.keys().collect::<Vec<_>>(), ["State"]);
+    }
+
+    #[test]
+    fn decode_reads_fields_at_their_offsets() {
+        let layouts = layouts(
+            r#"
+            #[repr(C)]
+            pub struct Counter { bump: u8, count: u64, delta: i16 }
+            "#,
+        );
+
+        let mut data = vec![0u8; 24];
+        data[0] = 254;
+        data[8..16].copy_from_slice(&42u64.to_le_bytes());
+        data[16..18].copy_from_slice(&(-3i16).to_le_bytes());
+
+        let fields = layouts.decode("Counter", &data).unwrap();
+        assert_eq!(
+            fields,
+            [
+                ("bump".to_string(), json!(254)),
+                ("count".to_string(), json!(42)),
+                ("delta".to_string(), json!(-3)),
+            ]
+        );
+        assert!(layouts.decode("Counter", &data[..10]).is_err());
+    }
+}
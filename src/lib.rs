@@ -0,0 +1,72 @@
+//! The library behind the `pinoc` CLI.
+//!
+//! Scaffold pinocchio programs and manage their keys from Rust:
+//!
+//! ```no_run
+//! use pinoc::{keys, InitOptions, Project};
+//!
+//! # fn main() -> pinoc::Result<()> {
+//! let project = Project::init(InitOptions::new("my-program"))?;
+//! keys::sync(&project)?;
+//!
+//! // like the CLI, find the project from any of its subdirectories
+//! let project = Project::discover("my-program/src")?;
+//! let config = pinoc::config::load(project.root())?;
+//! println!("{} deploys to {}", project.name(), config.provider.cluster);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Progress messages are off by default; call [`output::init`] to print them like the CLI does.
+
+#[macro_use]
+pub mod output;
+
+pub mod compat;
+pub mod config;
+pub mod error;
+pub mod keys;
+pub mod project;
+pub mod registry;
+
+// the commands of the pinoc binary, not part of the stable API
+#[doc(hidden)]
+pub mod account;
+#[doc(hidden)]
+pub mod adopt;
+#[doc(hidden)]
+pub mod call;
+#[doc(hidden)]
+pub mod check_deps;
+#[doc(hidden)]
+pub mod close;
+mod cluster;
+#[doc(hidden)]
+pub mod deploy;
+#[doc(hidden)]
+pub mod deployments;
+#[doc(hidden)]
+pub mod doctor;
+#[doc(hidden)]
+pub mod ecosystem;
+mod layout;
+#[doc(hidden)]
+pub mod pda;
+mod preflight;
+mod rpc;
+#[doc(hidden)]
+pub mod scripts;
+#[doc(hidden)]
+pub mod search;
+mod transaction;
+#[doc(hidden)]
+pub mod upgrade;
+#[doc(hidden)]
+pub mod verify;
+#[doc(hidden)]
+pub mod watch;
+#[doc(hidden)]
+pub mod wizard;
+
+pub use error::{PinocError, Result};
+pub use project::{InitOptions, Project};
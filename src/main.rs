@@ -1,23 +1,18 @@
-use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use serde::Deserialize;
-use std::fs;
-use std::path::Path;
-use std::process::Command;
-
-mod content;
-use content::templates;
-
-#[derive(Debug, Deserialize)]
-struct PinocConfig {
-    provider: ProviderConfig,
-}
-
-#[derive(Debug, Deserialize)]
-struct ProviderConfig {
-    cluster: String,
-    wallet: String,
-}
+use anyhow::Result;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use pinoc::config::{self, expand_tilde};
+use pinoc::keys::{self, SyncOutcome};
+use pinoc::output::{self, OutputConfig, Verbosity};
+use pinoc::project::{self, validate_project_name};
+use pinoc::{
+    account, adopt, call, check_deps, close, compat, deploy, deployments, doctor, ecosystem, pda,
+    registry, scripts, search, upgrade, verify, watch, wizard, InitOptions, PinocError, Project,
+};
+use pinoc::{out, status, verbose, warning};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -25,6 +20,49 @@ struct ProviderConfig {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        help = "Print newline-delimited JSON events instead of text (errors go to stderr with a code and hint)"
+    )]
+    json: bool,
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Only print results, warnings and errors"
+    )]
+    quiet: bool,
+    #[arg(
+        short,
+        long,
+        global = true,
+        conflicts_with = "quiet",
+        help = "Print extra details"
+    )]
+    verbose: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Disable colored output (also set by the NO_COLOR environment variable)"
+    )]
+    no_color: bool,
+    #[arg(long, global = true, help = "Print messages without emoji")]
+    no_emoji: bool,
+    #[arg(
+        short = 'C',
+        value_name = "DIR",
+        global = true,
+        help = "Run as if pinoc was started in DIR"
+    )]
+    directory: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        global = true,
+        help = "Path to the project's Cargo.toml or Pinoc.toml instead of searching parent directories"
+    )]
+    manifest_path: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -33,142 +71,534 @@ enum KeyCommands {
     Sync,
 }
 
+#[derive(Subcommand)]
+enum DeploymentCommands {
+    List {
+        #[arg(long, help = "Only list deployments to this cluster")]
+        cluster: Option<String>,
+    },
+    Diff {
+        #[arg(help = "Deployment to compare: <cluster>, <cluster>@<n> or local")]
+        from: String,
+        #[arg(help = "Deployment to compare against (defaults to the local build)")]
+        to: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
     Init {
-        project_name: String,
+        #[arg(
+            help = "Project name (asked for by the wizard when omitted), or '.' to adopt the current crate"
+        )]
+        project_name: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "template",
+            help = "Adopt an existing crate (the current directory, or the given path) instead of creating one"
+        )]
+        existing: bool,
         #[arg(long, help = "Don't initialize git")]
         no_git: bool,
+        #[arg(short, long, help = "Skip the interactive wizard and use the defaults")]
+        yes: bool,
         #[arg(long, help = "Create minimal project without tests and boilerplate")]
         no_boilerplate: bool,
+        #[arg(
+            long,
+            help = "Create the project from a template (built-in name, git URL or path)"
+        )]
+        template: Option<String>,
+        #[arg(
+            long,
+            requires = "template",
+            help = "Run the post-init hooks of a git or local template without asking"
+        )]
+        trust: bool,
+        #[arg(long, value_enum, help = "Test framework for the generated tests")]
+        tests: Option<registry::TestFramework>,
+        #[arg(long, conflicts_with = "tests", help = "Don't generate tests")]
+        no_tests: bool,
+        #[arg(long, help = "Don't use shank for IDL generation")]
+        no_shank: bool,
+        #[arg(long, help = "Don't log instruction names")]
+        no_logging: bool,
+        #[arg(long, help = "Pinocchio version to depend on")]
+        pinocchio_version: Option<String>,
+        #[arg(long, help = "Name of the first instruction (snake_case)")]
+        instruction: Option<String>,
+        #[arg(long, help = "Name of the first state account (PascalCase)")]
+        state: Option<String>,
+        #[arg(long, help = "Cluster written to Pinoc.toml")]
+        cluster: Option<String>,
+    },
+    Templates,
+    Doctor,
+    UpgradeDeps {
+        #[arg(
+            long,
+            help = "Pinocchio version to upgrade to (defaults to the latest known)"
+        )]
+        to: Option<String>,
+        #[arg(short, long, help = "Apply the changes without asking")]
+        yes: bool,
+        #[arg(long, help = "Only show the changes")]
+        dry_run: bool,
+    },
+    Build {
+        #[arg(
+            long,
+            help = "Build with the toolchain pinned in Pinoc.toml and write build-manifest.json"
+        )]
+        verifiable: bool,
+        #[arg(
+            long,
+            conflicts_with = "verifiable",
+            help = "Rebuild when src/, tests/ or Cargo.toml change"
+        )]
+        watch: bool,
+        #[arg(
+            long,
+            requires = "watch",
+            help = "Redeploy to localnet after each build"
+        )]
+        deploy: bool,
+    },
+    Test {
+        #[arg(help = "Only run tests whose name contains this")]
+        filter: Option<String>,
+        #[arg(
+            long,
+            help = "Rebuild and rerun the tests when src/, tests/ or Cargo.toml change"
+        )]
+        watch: bool,
+        #[arg(
+            long,
+            requires = "watch",
+            help = "Redeploy to localnet after the tests pass"
+        )]
+        deploy: bool,
     },
-    Build,
-    Test,
     Deploy {
         #[arg(long, help = "Cluster override")]
         cluster: Option<String>,
         #[arg(long, help = "Wallet override")]
         wallet: Option<String>,
+        #[arg(long, help = "Skip pre-flight checks")]
+        skip_checks: bool,
+    },
+    Upgrade {
+        #[arg(long, help = "Cluster override")]
+        cluster: Option<String>,
+        #[arg(long, help = "Wallet override")]
+        wallet: Option<String>,
+        #[arg(long, help = "Upgrade from an already written buffer account")]
+        buffer: Option<String>,
+    },
+    Close {
+        #[arg(long, help = "Cluster override")]
+        cluster: Option<String>,
+        #[arg(long, help = "Wallet override")]
+        wallet: Option<String>,
+        #[arg(
+            long,
+            help = "Account receiving the reclaimed lamports (defaults to the wallet)"
+        )]
+        recipient: Option<String>,
+        #[arg(long, help = "Only close buffer accounts owned by the wallet")]
+        buffers_only: bool,
     },
     Clean {
         #[arg(long, help = "Remove all files including keypair files")]
         no_preserve: bool,
     },
     Add {
+        #[arg(help = "Crate to add, optionally with a version (name@version)")]
         package_name: String,
+        #[arg(long, help = "Add as a dev-dependency")]
+        dev: bool,
     },
+    CheckDeps,
     Search {
         query: Option<String>,
+        #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u16).range(1..=100), help = "Maximum number of results")]
+        limit: u16,
+        #[arg(long, value_enum, help = "Sort by downloads or most recently updated")]
+        sort: Option<search::SearchSort>,
     },
     Keys {
         #[command(subcommand)]
         command: KeyCommands,
     },
+    Verify {
+        program_id: String,
+        #[arg(
+            long,
+            help = "Dumped on-chain program (dumped from the cluster when omitted)"
+        )]
+        so: Option<PathBuf>,
+        #[arg(long, help = "Cluster override")]
+        cluster: Option<String>,
+    },
+    Deployments {
+        #[command(subcommand)]
+        command: DeploymentCommands,
+    },
+    Account {
+        address: String,
+        #[arg(
+            long = "type",
+            help = "State struct to decode the data as (detected from the data length when omitted)"
+        )]
+        type_name: Option<String>,
+        #[arg(long, help = "Cluster override")]
+        cluster: Option<String>,
+    },
+    Pda {
+        #[arg(help = "State whose declared seeds derive the address, e.g. MyState")]
+        state: Option<String>,
+        #[arg(
+            long = "seed",
+            help = "Seed as <kind:value>: str, pubkey, hex, u8-u128 or i8-i128 (repeatable)"
+        )]
+        seeds: Vec<String>,
+        #[arg(long, help = "Pubkey seed of the state, e.g. its owner")]
+        owner: Option<String>,
+        #[arg(long, help = "Program ID (defaults to declare_id! in the program)")]
+        program: Option<String>,
+    },
+    Call {
+        #[arg(help = "Instruction to send, e.g. initialize")]
+        instruction: String,
+        #[arg(
+            long = "arg",
+            help = "Instruction data field as name=value (bump=auto takes the bump of the PDA account)"
+        )]
+        args: Vec<String>,
+        #[arg(
+            long = "account",
+            help = "Account as name=<wallet|pda:State|keypair:path|address|mut:address>"
+        )]
+        accounts: Vec<String>,
+        #[arg(long, help = "Cluster override")]
+        cluster: Option<String>,
+        #[arg(long, help = "Wallet override")]
+        wallet: Option<String>,
+        #[arg(long, help = "Program ID (defaults to declare_id! in the program)")]
+        program: Option<String>,
+    },
+    Run {
+        #[arg(help = "Script from [scripts] in Pinoc.toml (lists the scripts when omitted)")]
+        name: Option<String>,
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "Arguments passed to the script"
+        )]
+        args: Vec<String>,
+    },
     #[command(name = "--help")]
     Help,
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn main() -> ExitCode {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let command = command_name(&matches);
+
+    output::init(OutputConfig {
+        verbosity: if cli.quiet {
+            Verbosity::Quiet
+        } else if cli.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        },
+        json: cli.json,
+        color: !cli.no_color && std::env::var_os("NO_COLOR").is_none(),
+        emoji: !cli.no_emoji,
+    });
+
+    output::event("command_started", json!({ "command": command }));
+    match run(&cli) {
+        Ok(()) => {
+            output::event(
+                "command_finished",
+                json!({ "command": command, "success": true }),
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            let report = PinocError::report(&error);
+            if cli.json {
+                output::event(
+                    "command_finished",
+                    json!({ "command": command, "success": false, "code": report.code }),
+                );
+                eprintln!("{}", json!({ "error": report }));
+            } else {
+                eprintln!("Error: {}", report.message);
+                if let Some(hint) = &report.hint {
+                    warning!("💡 {}", hint);
+                }
+            }
+            ExitCode::from(report.exit_code as u8)
+        }
+    }
+}
+
+/// the subcommand path, like `keys sync`, reported in the JSON events
+fn command_name(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub_matches)) = current.subcommand() {
+        names.push(name);
+        current = sub_matches;
+    }
+    names.join(" ")
+}
+
+/// moves to the project root so commands can use paths relative to it
+fn enter_project(manifest_path: Option<&Path>) -> Result<()> {
+    let root = match manifest_path {
+        Some(manifest_path) => {
+            if !manifest_path.is_file() {
+                anyhow::bail!("Manifest not found: {}", manifest_path.display());
+            }
+            match manifest_path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                _ => PathBuf::from("."),
+            }
+        }
+        None => project::find_root(Path::new("."))?,
+    };
+
+    verbose!("📁 Project root: {}", root.display());
+    std::env::set_current_dir(&root).map_err(|source| PinocError::Io {
+        path: root.clone(),
+        source,
+    })?;
+    Ok(())
+}
+
+fn run(cli: &Cli) -> Result<()> {
+    if let Some(dir) = &cli.directory {
+        std::env::set_current_dir(dir).map_err(|source| PinocError::Io {
+            path: dir.clone(),
+            source,
+        })?;
+    }
+
+    match &cli.command {
+        Commands::Init { .. } | Commands::Templates | Commands::Search { .. } | Commands::Help => {}
+        // doctor also checks the tools outside of a project
+        Commands::Doctor if cli.manifest_path.is_none() => {
+            let _ = enter_project(None);
+        }
+        _ => enter_project(cli.manifest_path.as_deref())?,
+    }
 
     match &cli.command {
         Commands::Init {
             project_name,
+            existing: true,
+            ..
+        } => {
+            adopt::adopt_project(Path::new(project_name.as_deref().unwrap_or(".")))?;
+        }
+        Commands::Init {
+            project_name: Some(project_name),
+            ..
+        } if project_name == "." => {
+            adopt::adopt_project(Path::new("."))?;
+        }
+        Commands::Init {
+            project_name,
+            existing: _,
             no_git,
+            yes,
             no_boilerplate,
+            template,
+            trust,
+            tests,
+            no_tests,
+            no_shank,
+            no_logging,
+            pinocchio_version,
+            instruction,
+            state,
+            cluster,
         } => {
-            init_project(project_name, *no_git, *no_boilerplate)?;
-        }
-        Commands::Build => {
-            println!("Building program");
-            let status = Command::new("cargo")
-                .arg("build-sbf")
-                .spawn()?
-                .wait()
-                .with_context(|| "Failed to build project")?;
-
-            if !status.success() {
-                anyhow::bail!("Build failed with exit code: {:?}", status.code());
+            let has_options = *no_boilerplate
+                || template.is_some()
+                || tests.is_some()
+                || *no_tests
+                || *no_shank
+                || *no_logging
+                || pinocchio_version.is_some()
+                || instruction.is_some()
+                || state.is_some()
+                || cluster.is_some();
+
+            let choices = if !yes && !has_options && !cli.json && wizard::is_interactive() {
+                wizard::run(project_name.as_deref())?
             } else {
-                println!("Build completed successfully!");
-            }
-        }
-        Commands::Test => {
-            println!("Testing program");
-            let status = Command::new("cargo")
-                .arg("test")
-                .spawn()?
-                .wait()
-                .with_context(|| "Failed to test project")?;
+                let project_name = project_name.clone().ok_or_else(|| {
+                    anyhow::anyhow!("Missing project name. Usage: pinoc init <project_name>")
+                })?;
 
-            if !status.success() {
-                anyhow::bail!("Test failed with exit code: {:?}", status.code());
-            } else {
-                println!("Tested successfully!");
+                let defaults = registry::TemplateOptions::default();
+                let test_framework = if *no_tests {
+                    registry::TestFramework::None
+                } else {
+                    tests.unwrap_or(defaults.test_framework)
+                };
+                let options = registry::TemplateOptions {
+                    boilerplate: !no_boilerplate,
+                    test_framework,
+                    with_shank: !no_shank,
+                    with_logging: !no_logging,
+                    pinocchio_version: match pinocchio_version {
+                        Some(version) => compat::find_release(version)?.pinocchio.to_string(),
+                        None => defaults.pinocchio_version,
+                    },
+                    instruction_name: instruction.clone().unwrap_or(defaults.instruction_name),
+                    state_name: state.clone().unwrap_or(defaults.state_name),
+                    cluster: cluster.clone().unwrap_or(defaults.cluster),
+                };
+                wizard::validate_instruction_name(&options.instruction_name)?;
+                wizard::validate_state_name(&options.state_name)?;
+
+                wizard::InitChoices {
+                    project_name,
+                    template: template
+                        .clone()
+                        .unwrap_or_else(|| registry::DEFAULT_TEMPLATE.to_string()),
+                    options,
+                }
+            };
+
+            init_project(choices, *no_git, *trust)?;
+        }
+        Commands::Templates => {
+            registry::list_templates()?;
+        }
+        Commands::Doctor => {
+            doctor::run_doctor()?;
+        }
+        Commands::UpgradeDeps { to, yes, dry_run } => {
+            compat::upgrade_deps(to.as_deref(), *yes, *dry_run)?;
+        }
+        Commands::Build {
+            verifiable: true, ..
+        } => {
+            scripts::run_hook(scripts::PRE_BUILD, None, None)?;
+            verify::verifiable_build()?;
+            scripts::run_hook(scripts::POST_BUILD, None, None)?;
+        }
+        Commands::Build {
+            watch: true,
+            deploy,
+            ..
+        } => {
+            let options = watch::WatchOptions {
+                deploy: *deploy,
+                ..Default::default()
+            };
+            watch::watch(&Project::open(".")?, &options)?;
+        }
+        Commands::Build { .. } => {
+            status!("Building program");
+            let project = Project::open(".")?;
+            scripts::run_hook(scripts::PRE_BUILD, None, None)?;
+            output::step_started("build");
+            project.build()?;
+            output::step_finished("build");
+            status!("Build completed successfully!");
+            scripts::run_hook(scripts::POST_BUILD, None, None)?;
+        }
+        Commands::Test {
+            filter,
+            watch: true,
+            deploy,
+        } => {
+            let options = watch::WatchOptions {
+                test: true,
+                filter: filter.clone(),
+                deploy: *deploy,
+            };
+            watch::watch(&Project::open(".")?, &options)?;
+        }
+        Commands::Test { filter, .. } => {
+            status!("Testing program");
+            let project = Project::open(".")?;
+            output::step_started("test");
+            match filter {
+                Some(filter) => project.test_matching(filter)?,
+                None => project.test()?,
             }
+            output::step_finished("test");
+            status!("Tested successfully!");
         }
-        Commands::Deploy { cluster, wallet } => {
-            println!("Deploying program");
-
-            let config = read_pinoc_config()?;
+        Commands::Deploy {
+            cluster,
+            wallet,
+            skip_checks,
+        } => {
+            scripts::run_hook(scripts::PRE_DEPLOY, cluster.as_deref(), wallet.as_deref())?;
+            deploy::deploy_program(cluster.as_deref(), wallet.as_deref(), *skip_checks)?;
+            scripts::run_hook(scripts::POST_DEPLOY, cluster.as_deref(), wallet.as_deref())?;
+        }
+        Commands::Run { name: None, .. } => {
+            scripts::list_scripts()?;
+        }
+        Commands::Run {
+            name: Some(name),
+            args,
+        } => {
+            scripts::run_script(name, args)?;
+        }
+        Commands::Upgrade {
+            cluster,
+            wallet,
+            buffer,
+        } => {
+            let config = config::load(Path::new("."))?;
 
             let cluster_url = cluster.as_deref().unwrap_or(&config.provider.cluster);
             let wallet_path = wallet.as_deref().unwrap_or(&config.provider.wallet);
 
-            println!("📋 Using configuration:");
-            println!("   Cluster: {}", cluster_url);
-            println!("   Wallet: {}", wallet_path);
-
-            let target_deploy_dir = Path::new("target/deploy");
-            if !target_deploy_dir.exists() {
-                anyhow::bail!("target/deploy directory not found. Please run 'pinoc build' first.");
-            }
+            upgrade::upgrade_program(cluster_url, &expand_tilde(wallet_path)?, buffer.as_deref())?;
+        }
+        Commands::Close {
+            cluster,
+            wallet,
+            recipient,
+            buffers_only,
+        } => {
+            let config = config::load(Path::new("."))?;
 
-            let mut so_file = None;
-            for entry in fs::read_dir(target_deploy_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("so") {
-                    so_file = Some(path);
-                    break;
-                }
-            }
+            let cluster_url = cluster.as_deref().unwrap_or(&config.provider.cluster);
+            let wallet_path = wallet.as_deref().unwrap_or(&config.provider.wallet);
 
-            let so_path = so_file.ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No .so file found in target/deploy. Please run 'pinoc build' first."
-                )
-            })?;
-
-            let mut deploy_cmd = Command::new("solana");
-            deploy_cmd
-                .arg("program")
-                .arg("deploy")
-                .arg("--url")
-                .arg(cluster_url)
-                .arg("--keypair")
-                .arg(&expand_tilde(wallet_path)?)
-                .arg(&so_path);
-
-            let status = deploy_cmd
-                .spawn()?
-                .wait()
-                .with_context(|| "Failed to deploy program")?;
-
-            if !status.success() {
-                anyhow::bail!("Deploy failed with exit code: {:?}", status.code());
-            } else {
-                println!("Program deployed successfully!");
-            }
+            close::close_program(
+                cluster_url,
+                &expand_tilde(wallet_path)?,
+                recipient.as_deref(),
+                *buffers_only,
+            )?;
         }
         Commands::Clean { no_preserve } => {
             clean_project(*no_preserve)?;
         }
-        Commands::Add { package_name } => {
-            add_package(package_name)?;
+        Commands::Add { package_name, dev } => {
+            ecosystem::add_package(package_name, *dev)?;
+        }
+        Commands::CheckDeps => {
+            check_deps::check_deps()?;
         }
-        Commands::Search { query } => {
-            search_packages(query.as_deref())?;
+        Commands::Search { query, limit, sort } => {
+            search::search_packages(query.as_deref(), usize::from(*limit), *sort)?;
         }
         Commands::Keys { command } => match command {
             KeyCommands::List => {
@@ -178,6 +608,68 @@ fn main() -> Result<()> {
                 sync_program_keys()?;
             }
         },
+        Commands::Verify {
+            program_id,
+            so,
+            cluster,
+        } => {
+            let config = config::load(Path::new("."))?;
+            let cluster_url = cluster.as_deref().unwrap_or(&config.provider.cluster);
+
+            verify::verify_program(program_id, so.as_deref(), cluster_url)?;
+        }
+        Commands::Account {
+            address,
+            type_name,
+            cluster,
+        } => {
+            let config = config::load(Path::new("."))?;
+            let cluster = cluster.as_deref().unwrap_or(&config.provider.cluster);
+
+            account::show_account(address, type_name.as_deref(), cluster)?;
+        }
+        Commands::Pda {
+            state,
+            seeds,
+            owner,
+            program,
+        } => {
+            pda::show_pda(
+                state.as_deref(),
+                seeds,
+                owner.as_deref(),
+                program.as_deref(),
+            )?;
+        }
+        Commands::Call {
+            instruction,
+            args,
+            accounts,
+            cluster,
+            wallet,
+            program,
+        } => {
+            let config = config::load(Path::new("."))?;
+            let cluster = cluster.as_deref().unwrap_or(&config.provider.cluster);
+            let wallet = wallet.as_deref().unwrap_or(&config.provider.wallet);
+
+            call::call_instruction(
+                instruction,
+                args,
+                accounts,
+                cluster,
+                wallet,
+                program.as_deref(),
+            )?;
+        }
+        Commands::Deployments { command } => match command {
+            DeploymentCommands::List { cluster } => {
+                deployments::list_deployments(cluster.as_deref())?;
+            }
+            DeploymentCommands::Diff { from, to } => {
+                deployments::diff_deployments(from, to.as_deref())?;
+            }
+        },
         Commands::Help => {
             display_help_banner()?;
         }
@@ -188,7 +680,7 @@ fn main() -> Result<()> {
 
 fn display_help_banner() -> Result<()> {
     // banner
-    println!(
+    out!(
         r#"
        _                   
  _ __ (_)_ __   ___   ___  
@@ -199,35 +691,48 @@ fn display_help_banner() -> Result<()> {
  "#
     );
 
-    println!("👾 Setup your pinocchio project blazingly fast💨");
-
-    println!("\n🏗️ AVAILABLE COMMANDS:");
-    println!("   pinoc init <project_name> [--no-git] [--no-boilerplate] - Initialize a new Pinocchio project");
-    println!("   pinoc build               - Build the project");
-    println!("   pinoc test                - Run project tests");
-    println!("   pinoc deploy [--cluster] [--wallet] - Deploy the project (uses Pinoc.toml config, optional overrides)");
-    println!(
-        "   pinoc clean [--no-preserve] - Clean target directory (preserves keypairs by default)"
-    );
-    println!("   pinoc add <package_name>  - Add a package to the project");
-    println!("   pinoc search [query]      - Search for pinocchio packages on crates.io");
-    println!("   pinoc keys list           - List program keypairs");
-    println!("   pinoc keys sync           - Sync program ID with keypair");
+    out!("👾 Setup your pinocchio project blazingly fast💨");
+
+    out!("\n🏗️ AVAILABLE COMMANDS:");
+    out!("   pinoc init [project_name] [--yes] [--no-git] [--no-boilerplate] [--template] [--trust] [--tests] [--no-shank] [--no-logging] - Initialize a new Pinocchio project");
+    out!("   pinoc init . / pinoc init --existing - Adopt the existing crate in the current directory");
+    out!("   pinoc templates           - List available project templates");
+    out!("   pinoc upgrade-deps [--to] [--dry-run] [--yes] - Upgrade pinocchio and its companion crates");
+    out!("   pinoc doctor              - Check installed tools and the project configuration");
+    out!("   pinoc build [--verifiable] [--watch] [--deploy] - Build the project, or rebuild on every change");
+    out!("   pinoc test [filter] [--watch] [--deploy] - Run project tests, or rerun them on every change");
+    out!("   pinoc deploy [--cluster] [--wallet] [--skip-checks] - Deploy the project (uses Pinoc.toml config, optional overrides)");
+    out!("   pinoc upgrade [--cluster] [--wallet] [--buffer] - Upgrade a deployed program through a buffer account");
+    out!("   pinoc close [--cluster] [--wallet] [--recipient] [--buffers-only] - Close the program and its buffers, reclaiming rent");
+    out!("   pinoc clean [--no-preserve] - Clean target directory (preserves keypairs by default)");
+    out!("   pinoc add <package_name> [--dev] - Add a package with a version and features that fit the project");
+    out!("   pinoc check-deps          - Flag dependencies that need std or an allocator");
+    out!("   pinoc search [query] [--limit] [--sort] - Search for pinocchio packages on crates.io");
+    out!("   pinoc keys list           - List program keypairs");
+    out!("   pinoc keys sync           - Sync program ID with keypair");
+    out!("   pinoc verify <program_id> [--so] [--cluster] - Verify an on-chain program against a local verifiable build");
+    out!("   pinoc account <address> [--type] [--cluster] - Fetch an account and decode it with the program's state structs");
+    out!("   pinoc pda [state] [--seed] [--owner] [--program] - Derive a program address and its bump");
+    out!("   pinoc call <instruction> [--arg] [--account] [--cluster] [--wallet] [--program] - Send an instruction of the program");
+    out!("   pinoc run [name] [args...] - Run a script from [scripts] in Pinoc.toml, or list them");
+    out!("   pinoc deployments list [--cluster] - List recorded deployments");
+    out!("   pinoc deployments diff <from> [to] - Compare deployments or a deployment with the local build");
+
+    out!("\n⚙️  GLOBAL OPTIONS:");
+    out!("   --json                    - Print newline-delimited JSON events and errors (exit codes are listed in the README)");
+    out!("   -C <dir>                  - Run as if pinoc was started in <dir>");
+    out!("   --manifest-path <path>    - Use this Cargo.toml or Pinoc.toml instead of searching parent directories");
+    out!("   -q, --quiet               - Only print results, warnings and errors");
+    out!("   -v, --verbose             - Also print the commands pinoc runs");
+    out!("   --no-color / --no-emoji   - Plain output for logs and CI (NO_COLOR is honored too)");
 
     Ok(())
 }
 
-fn init_project(project_name: &str, no_git: bool, no_boilerplate: bool) -> Result<()> {
-    // Validate project name - only allow alphanumeric characters and underscores
-    if !is_valid_project_name(project_name) {
-        anyhow::bail!(
-            "Invalid project name '{}'. Project names can only contain letters, numbers, and underscores (_). \
-            Hyphens (-) and other special characters are not allowed.",
-            project_name
-        );
-    }
+fn init_project(choices: wizard::InitChoices, no_git: bool, trust_hooks: bool) -> Result<()> {
+    validate_project_name(&choices.project_name)?;
 
-    println!(
+    status!(
         r#"
        _                   
  _ __ (_)_ __   ___   ___  
@@ -237,504 +742,103 @@ fn init_project(project_name: &str, no_git: bool, no_boilerplate: bool) -> Resul
 |_|                       
  "#
     );
-    println!("🧑🏻‍🍳 Initializing your pinocchio project: {}", project_name);
-    println!(""); // Create the project directory
-    let project_dir = Path::new(project_name);
-    fs::create_dir_all(project_dir)
-        .with_context(|| format!("Failed to create project directory: {}", project_name))?;
-
-    // init new cargo project inside
-    let mut cargo_init = Command::new("cargo");
-    cargo_init
-        .arg("init")
-        .arg("--lib")
-        .arg("--name")
-        .arg(project_name);
-
-    if no_git {
-        cargo_init.arg("--vcs").arg("none");
-    }
-
-    let output = cargo_init
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to run 'cargo init'")?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to initialize Cargo project: {}", error);
-    }
-
-    let deploy_dir = project_dir.join("target").join("deploy");
-    fs::create_dir_all(&deploy_dir)?;
-
-    // generate keypair
-    let keypair_path = format!("./target/deploy/{}-keypair.json", project_name);
-    let keygen_output = Command::new("solana-keygen")
-        .arg("new")
-        .arg("-o")
-        .arg(&keypair_path)
-        .arg("--no-bip39-passphrase") // skip the passphrase prompt
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to generate keypair")?;
-
-    if !keygen_output.status.success() {
-        let error = String::from_utf8_lossy(&keygen_output.stderr);
-        anyhow::bail!("Failed to generate keypair: {}", error);
-    }
-
-    let address_output = Command::new("solana")
-        .arg("address")
-        .arg("-k")
-        .arg(&keypair_path)
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to read keypair address")?;
-
-    let program_address: String;
-    if address_output.status.success() {
-        program_address = String::from_utf8_lossy(&address_output.stdout)
-            .trim()
-            .to_string();
-        println!("Generated program address: {}", program_address);
-    } else {
-        let error = String::from_utf8_lossy(&address_output.stderr);
-        anyhow::bail!("Failed to get program address from keypair: {}", error);
-    }
-
-    let user_address_output = Command::new("solana")
-        .arg("address")
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to get user address")?;
-
-    let user_address: String;
-    if user_address_output.status.success() {
-        user_address = String::from_utf8_lossy(&user_address_output.stdout)
-            .trim()
-            .to_string();
-    } else {
-        let error = String::from_utf8_lossy(&user_address_output.stderr);
-        println!("Failed to get user Solana address: {}", error);
-        user_address = String::new();
-    }
-
-    if no_boilerplate {
-        create_minimal_project_structure(project_dir, project_name, program_address.clone())?;
-    } else {
-        create_project_structure(project_dir, user_address, program_address.clone())?;
-        update_cargo_toml(project_dir, project_name)?;
-    }
-
-    if !no_git {
-        init_git_repo(project_dir, project_name)?;
-    }
-
-    println!("");
-    println!(
+    status!(
+        "🧑🏻‍🍳 Initializing your pinocchio project: {}",
+        choices.project_name
+    );
+    status!();
+
+    let project = Project::init(InitOptions {
+        template: choices.template,
+        template_options: choices.options,
+        git: !no_git,
+        trust_hooks,
+        ..InitOptions::new(choices.project_name)
+    })?;
+    let program_id = project.program_id()?;
+    output::artifact("project", project.root());
+    output::artifact("keypair", &project.keypair_path());
+    output::address("program", &program_id);
+    out!("Generated program address: {}", program_id);
+
+    status!();
+    status!(
         "✅ Pinocchio Project '{}' initialized successfully!",
-        project_name
+        project.name()
     );
-    println!("\n📋 Next steps:");
-    println!("$ cd {}", project_name);
-    println!("$ pinoc build");
-    println!("$ pinoc test");
-    println!("$ pinoc deploy");
-    println!("");
-
-    Ok(())
-}
-
-fn create_minimal_project_structure(
-    project_dir: &Path,
-    project_name: &str,
-    program_address: String,
-) -> Result<()> {
-    println!("📦 Creating minimal project structure...");
-
-    fs::write(
-        project_dir.join("Cargo.toml"),
-        templates::minimal_templates::minimal_cargo_toml(project_name),
-    )?;
-
-    fs::write(project_dir.join(".gitignore"), templates::gitignore())?;
-
-    let src_dir = project_dir.join("src");
-    fs::create_dir_all(&src_dir)?;
-
-    fs::write(
-        src_dir.join("lib.rs"),
-        templates::minimal_templates::minimal_lib_rs(&program_address),
-    )?;
-
-    fs::write(
-        project_dir.join("README.md"),
-        templates::minimal_templates::minimal_readme_md(project_name),
-    )?;
-
-    fs::write(project_dir.join("Pinoc.toml"), templates::pinoc_toml())?;
-
-    println!("✅ Minimal project structure created!");
-    println!("📁 Only essential files generated: Cargo.toml, src/lib.rs, README.md, .gitignore, Pinoc.toml");
+    status!("\n📋 Next steps:");
+    status!("$ cd {}", project.name());
+    status!("$ pinoc build");
+    status!("$ pinoc test");
+    status!("$ pinoc deploy");
+    status!();
 
     Ok(())
 }
 
-/// validates that the project name only contains alphanumeric characters and underscores
-fn is_valid_project_name(name: &str) -> bool {
-    if name.is_empty() {
-        return false;
-    }
-
-    name.chars().all(|c| c.is_alphanumeric() || c == '_')
-}
-
-fn init_git_repo(project_dir: &Path, project_name: &str) -> Result<()> {
-    let git_init_output = Command::new("git")
-        .arg("init")
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to initialize git repository")?;
-
-    if !git_init_output.status.success() {
-        let error = String::from_utf8_lossy(&git_init_output.stderr);
-        println!("Warning: Failed to initialize git repository: {}", error);
-        return Ok(());
-    }
-
-    let git_add_output = Command::new("git")
-        .arg("add")
-        .arg(".")
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to add files to git")?;
-
-    if !git_add_output.status.success() {
-        let error = String::from_utf8_lossy(&git_add_output.stderr);
-        println!("Warning: Failed to add files to git: {}", error);
-        return Ok(());
-    }
-
-    let commit_message = format!("Initial commit: Setup Pinocchio project '{}'", project_name);
-    let git_commit_output = Command::new("git")
-        .arg("commit")
-        .arg("-m")
-        .arg(&commit_message)
-        .current_dir(project_dir)
-        .output()
-        .with_context(|| "Failed to make initial commit")?;
-
-    if !git_commit_output.status.success() {
-        let error = String::from_utf8_lossy(&git_commit_output.stderr);
-        println!("Warning: Failed to make initial commit: {}", error);
-        // Check if it's because of missing git config
-        if error.contains("user.email") || error.contains("user.name") {
-            println!("Hint: Set your git config with:");
-            println!("  git config --global user.email \"you@example.com\"");
-            println!("  git config --global user.name \"Your Name\"");
-        }
-        return Ok(());
-    }
-    Ok(())
-}
-
-fn create_project_structure(
-    project_dir: &Path,
-    user_address: String,
-    program_address: String,
-) -> Result<()> {
-    fs::write(project_dir.join("README.md"), templates::readme_md())?;
-    fs::write(project_dir.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_dir.join("Pinoc.toml"), templates::pinoc_toml())?;
-
-    let src_dir = project_dir.join("src");
-    fs::create_dir_all(&src_dir)?;
-
-    fs::write(
-        src_dir.join("lib.rs"),
-        templates::lib_rs(program_address.as_str()),
-    )?;
-
-    fs::write(src_dir.join("entrypoint.rs"), templates::entrypoint_rs())?;
-
-    fs::write(src_dir.join("errors.rs"), templates::errors_rs())?;
-
-    let instructions_dir = src_dir.join("instructions");
-    fs::create_dir_all(&instructions_dir)?;
-
-    fs::write(
-        instructions_dir.join("mod.rs"),
-        templates::instructions::instructions_mod_rs(),
-    )?;
-    fs::write(
-        instructions_dir.join("initialize.rs"),
-        templates::instructions::initialize(),
-    )?;
-
-    let states_dir = src_dir.join("states");
-    fs::create_dir_all(&states_dir)?;
-
-    fs::write(
-        states_dir.join("mod.rs"),
-        templates::states::states_mod_rs(),
-    )?;
-    fs::write(states_dir.join("utils.rs"), templates::states::utils_rs())?;
-
-    fs::write(states_dir.join("state.rs"), templates::states::state_rs())?;
-
-    let test_dir = project_dir.join("tests");
-    fs::create_dir_all(&test_dir)?;
-
-    let test_address = &user_address;
-
-    let project_name = project_dir
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or("project");
-
-    fs::write(
-        test_dir.join("tests.rs"),
-        templates::unit_tests::unit_test_rs(test_address, &program_address, project_name),
-    )?;
-
-    Ok(())
-}
-
-fn update_cargo_toml(project_dir: &Path, project_name: &str) -> Result<()> {
-    fs::write(
-        project_dir.join("Cargo.toml"),
-        templates::cargo_toml(project_name),
-    )?;
-
-    Ok(())
-}
-
-fn add_package(package_name: &str) -> Result<()> {
-    // Check if Cargo.toml exists
-    let cargo_toml_path = Path::new("Cargo.toml");
-    if !cargo_toml_path.exists() {
-        anyhow::bail!(
-            "Cargo.toml not found. Please run this command from the project root directory."
-        );
-    }
-
-    // Add the package using cargo add
-    println!("📦 Adding package: {}", package_name);
-    let status = Command::new("cargo")
-        .arg("add")
-        .arg(package_name)
-        .spawn()?
-        .wait()
-        .with_context(|| format!("Failed to add package: {}", package_name))?;
-
-    if !status.success() {
-        anyhow::bail!(
-            "Failed to add package '{}' with exit code: {:?}",
-            package_name,
-            status.code()
-        );
-    } else {
-        println!("✅ Package '{}' added successfully!", package_name);
-    }
-
-    Ok(())
-}
-
-fn search_packages(query: Option<&str>) -> Result<()> {
-    let search_term = match query {
-        Some(q) => format!("pinocchio {}", q),
-        None => "pinocchio".to_string(),
-    };
-
-    println!("🔍 Searching for packages matching '{}'...\n", search_term);
-
-    // Run cargo search
-    let output = Command::new("cargo")
-        .arg("search")
-        .arg(&search_term)
-        .arg("--limit")
-        .arg("20")
-        .output()
-        .with_context(|| "Failed to run 'cargo search'. Make sure cargo is installed.")?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("cargo search failed: {}", error);
-    }
-
-    let search_results = String::from_utf8_lossy(&output.stdout);
-    let packages = parse_cargo_search_output(&search_results)?;
-
-    if packages.is_empty() {
-        println!("No packages found matching '{}'.", search_term);
-        println!("💡 Try a different search term or check https://crates.io for more packages.");
-        return Ok(());
-    }
-
-    println!("📦 Found {} package(s):\n", packages.len());
-
-    for package in packages {
-        println!("🔹 {}", package.name);
-        println!("   Description: {}", package.description);
-        println!("   Version: {}", package.version);
-        println!("   Install: pinoc add {}", package.name);
-        println!();
-    }
-
-    Ok(())
-}
-
-#[derive(Debug)]
-struct SearchResult {
-    name: String,
-    description: String,
-    version: String,
-}
-
-fn parse_cargo_search_output(output: &str) -> Result<Vec<SearchResult>> {
-    let mut packages = Vec::new();
-
-    for line in output.lines() {
-        if line.trim().is_empty() || line.starts_with("...") {
-            continue;
-        }
-
-        if let Some(equals_pos) = line.find(" = ") {
-            let name = line[..equals_pos].trim().to_string();
-            let rest = &line[equals_pos + 3..];
-
-            if let Some(quote_end) = rest[1..].find('"') {
-                let version = rest[1..quote_end + 1].to_string();
-                let description = if let Some(hash_pos) = rest.find(" # ") {
-                    rest[hash_pos + 3..].trim().to_string()
-                } else {
-                    "No description available".to_string()
-                };
-
-                packages.push(SearchResult {
-                    name,
-                    description,
-                    version,
-                });
-            }
-        }
-    }
-
-    Ok(packages)
-}
-
 fn clean_project(no_preserve: bool) -> Result<()> {
-    println!("🧹 Cleaning project...");
+    status!("🧹 Cleaning project...");
 
-    let target_dir = Path::new("target");
-    if !target_dir.exists() {
-        println!("✅ No target directory found. Nothing to clean.");
+    let project = Project::open(".")?;
+    if !project.target_dir().exists() {
+        status!("✅ No target directory found. Nothing to clean.");
         return Ok(());
     }
 
-    let deploy_dir = target_dir.join("deploy");
-    let mut preserved_keypairs = Vec::new();
-
-    if !no_preserve && deploy_dir.exists() {
-        for entry in fs::read_dir(&deploy_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if let Some(file_name) = path.file_name() {
-                if let Some(name_str) = file_name.to_str() {
-                    if name_str.ends_with("-keypair.json") {
-                        let keypair_name = name_str.to_string();
-                        let keypair_content = fs::read(&path)
-                            .with_context(|| format!("Failed to read keypair: {}", name_str))?;
-                        preserved_keypairs.push((keypair_name, keypair_content));
-                        println!("🔐 Preserving keypair: {}", name_str);
-                    }
-                }
-            }
-        }
+    let preserved_keypairs = project.clean(!no_preserve)?;
+    for keypair_name in &preserved_keypairs {
+        output::artifact("keypair", &project.deploy_dir().join(keypair_name));
+        status!("🔐 Preserving keypair: {}", keypair_name);
     }
 
-    fs::remove_dir_all(target_dir).with_context(|| "Failed to remove target directory")?;
-
-    if !no_preserve {
-        fs::create_dir_all(&deploy_dir)
-            .with_context(|| "Failed to recreate target/deploy directory")?;
-
-        let keypair_count = preserved_keypairs.len();
-        for (keypair_name, keypair_content) in preserved_keypairs {
-            let new_path = deploy_dir.join(&keypair_name);
-            fs::write(&new_path, keypair_content)
-                .with_context(|| format!("Failed to restore keypair: {}", keypair_name))?;
-        }
-
-        println!("✅ Project cleaned successfully!");
-        if keypair_count > 0 {
-            println!("🔐 Preserved {} keypair file(s)", keypair_count);
-        } else {
-            println!("✅ Project cleaned successfully!");
-        }
+    if no_preserve {
+        status!("✅ Project cleaned successfully! (keypairs not preserved)");
     } else {
-        println!("✅ Project cleaned successfully! (keypairs not preserved)");
+        status!("✅ Project cleaned successfully!");
+        if !preserved_keypairs.is_empty() {
+            status!("🔐 Preserved {} keypair file(s)", preserved_keypairs.len());
+        }
     }
 
     Ok(())
 }
 
 fn list_program_keys() -> Result<()> {
-    println!("🔑 Listing program keys...");
+    status!("🔑 Listing program keys...");
 
-    let deploy_dir = Path::new("target/deploy");
-    if !deploy_dir.exists() {
-        println!("❌ No target/deploy directory found. Run 'pinoc build' first.");
+    let project = Project::open(".")?;
+    if !project.deploy_dir().exists() {
+        status!(
+            "❌ No {} directory found. Run 'pinoc build' first.",
+            project.deploy_dir().display()
+        );
         return Ok(());
     }
 
-    let mut found_keys = Vec::new();
-
-    for entry in fs::read_dir(deploy_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if let Some(file_name) = path.file_name() {
-            if let Some(name_str) = file_name.to_str() {
-                if name_str.ends_with("-keypair.json") {
-                    let program_name = name_str.replace("-keypair.json", "");
-
-                    // Get the public key from the keypair
-                    let address_output = Command::new("solana")
-                        .arg("address")
-                        .arg("-k")
-                        .arg(&path)
-                        .output()
-                        .with_context(|| format!("Failed to read keypair address: {}", name_str))?;
-
-                    if address_output.status.success() {
-                        let pubkey = String::from_utf8_lossy(&address_output.stdout)
-                            .trim()
-                            .to_string();
-                        found_keys.push((program_name, pubkey, path));
-                    }
-                }
-            }
-        }
-    }
-
+    let found_keys = keys::list(&project)?;
     if found_keys.is_empty() {
-        println!("❌ No program keypairs found in target/deploy/");
-        println!("💡 Run 'pinoc build' to generate keypairs");
+        status!(
+            "❌ No program keypairs found in {}",
+            project.deploy_dir().display()
+        );
+        status!("💡 Run 'pinoc build' to generate keypairs");
         return Ok(());
     }
 
-    println!("\n📋 Program Keys:");
-    println!("{:<20} {:<50} {}", "Program", "Public Key", "Keypair File");
-    println!("{:-<20} {:-<50} {:-<30}", "", "", "");
+    out!("\n📋 Program Keys:");
+    out!("{:<20} {:<50} Keypair File", "Program", "Public Key");
+    out!("{:-<20} {:-<50} {:-<30}", "", "", "");
 
-    for (program_name, pubkey, keypair_path) in found_keys {
-        println!(
+    for key in found_keys {
+        output::event(
+            "program_key",
+            json!({ "name": key.name, "pubkey": key.pubkey, "path": key.path }),
+        );
+        out!(
             "{:<20} {:<50} {}",
-            program_name,
-            pubkey,
-            keypair_path.file_name().unwrap().to_str().unwrap()
+            key.name,
+            key.pubkey,
+            key.path.file_name().unwrap_or_default().to_string_lossy()
         );
     }
 
@@ -742,155 +846,41 @@ fn list_program_keys() -> Result<()> {
 }
 
 fn sync_program_keys() -> Result<()> {
-    println!("🔄 Syncing program keys...");
-
-    let cargo_toml = Path::new("Cargo.toml");
-    if !cargo_toml.exists() {
-        anyhow::bail!("Cargo.toml not found. Please run this command from a project root.");
-    }
-
-    let cargo_content =
-        fs::read_to_string(cargo_toml).with_context(|| "Failed to read Cargo.toml")?;
-
-    let project_name = extract_project_name(&cargo_content)
-        .ok_or_else(|| anyhow::anyhow!("Could not find project name in Cargo.toml"))?;
-
-    let keypair_path = format!("target/deploy/{}-keypair.json", project_name);
-    let keypair_file = Path::new(&keypair_path);
-
-    if !keypair_file.exists() {
-        anyhow::bail!(
-            "Keypair file not found: {}. Run 'pinoc build' first.",
-            keypair_path
-        );
-    }
-
-    // Get the actual program public key
-    let address_output = Command::new("solana")
-        .arg("address")
-        .arg("-k")
-        .arg(&keypair_path)
-        .output()
-        .with_context(|| "Failed to read keypair address")?;
-
-    if !address_output.status.success() {
-        anyhow::bail!("Failed to get program address from keypair");
-    }
-
-    let actual_pubkey = String::from_utf8_lossy(&address_output.stdout)
-        .trim()
-        .to_string();
-
-    // Find and update the declare_id! macro in lib.rs
-    let lib_rs_path = Path::new("src/lib.rs");
-    if !lib_rs_path.exists() {
-        anyhow::bail!("src/lib.rs not found");
-    }
-
-    let lib_content =
-        fs::read_to_string(lib_rs_path).with_context(|| "Failed to read src/lib.rs")?;
-
-    // Check if the key is already consistent
-    if let Some(current_pubkey) = extract_current_program_id(&lib_content) {
-        if current_pubkey == actual_pubkey {
-            println!("✅ Program key is already consistent!");
-            println!("🔑 Program ID: {}", actual_pubkey);
-            println!("📝 No update needed in src/lib.rs");
-            return Ok(());
-        } else {
-            println!("🔄 Program key mismatch detected:");
-            println!("   Current in lib.rs: {}", current_pubkey);
-            println!("   Actual keypair:    {}", actual_pubkey);
+    status!("🔄 Syncing program keys...");
+
+    let project = Project::open(".")?;
+    let lib_path = project.lib_path();
+    let lib_path = lib_path.strip_prefix(project.root()).unwrap_or(&lib_path);
+    match keys::sync(&project)? {
+        SyncOutcome::Unchanged { program_id } => {
+            output::address("program", &program_id);
+            status!("✅ Program key is already consistent!");
+            out!("🔑 Program ID: {}", program_id);
+            status!("📝 No update needed in {}", lib_path.display());
         }
-    }
-
-    if let Some(updated_content) = update_declare_id(&lib_content, &actual_pubkey) {
-        fs::write(lib_rs_path, updated_content)
-            .with_context(|| "Failed to write updated src/lib.rs")?;
-
-        println!("✅ Successfully synced program key!");
-        println!("🔑 Program ID: {}", actual_pubkey);
-        println!("📝 Updated src/lib.rs with new program ID");
-    } else {
-        println!("⚠️  No declare_id! macro found in src/lib.rs");
-        println!("💡 Add this line to your lib.rs:");
-        println!("   pinocchio_pubkey::declare_id!(\"{}\");", actual_pubkey);
-    }
-
-    Ok(())
-}
-
-fn extract_project_name(cargo_content: &str) -> Option<String> {
-    for line in cargo_content.lines() {
-        if line.trim().starts_with("name = ") {
-            if let Some(name) = line.split('=').nth(1) {
-                return Some(name.trim().trim_matches('"').to_string());
-            }
-        }
-    }
-    None
-}
-
-fn update_declare_id(lib_content: &str, new_pubkey: &str) -> Option<String> {
-    let mut updated = false;
-    let mut lines = Vec::new();
-
-    for line in lib_content.lines() {
-        if line.contains("declare_id!") {
-            lines.push(format!(
-                "pinocchio_pubkey::declare_id!(\"{}\");",
-                new_pubkey
-            ));
-            updated = true;
-        } else {
-            lines.push(line.to_string());
+        SyncOutcome::Updated {
+            previous,
+            program_id,
+        } => {
+            output::event(
+                "declare_id_updated",
+                json!({ "previous": previous, "program_id": program_id }),
+            );
+            output::address("program", &program_id);
+            status!("🔄 Program key mismatch detected:");
+            status!("   Current in lib.rs: {}", previous);
+            status!("   Actual keypair:    {}", program_id);
+            status!("✅ Successfully synced program key!");
+            out!("🔑 Program ID: {}", program_id);
+            status!("📝 Updated {} with new program ID", lib_path.display());
         }
-    }
-
-    if updated {
-        Some(lines.join("\n"))
-    } else {
-        None
-    }
-}
-
-fn extract_current_program_id(lib_content: &str) -> Option<String> {
-    for line in lib_content.lines() {
-        if line.contains("declare_id!") {
-            // Look for the pattern: declare_id!("...") or pinocchio_pubkey::declare_id!("...")
-            if let Some(start) = line.find("declare_id!(\"") {
-                let after_declare = &line[start + 13..]; // Skip "declare_id!(\""
-                if let Some(end) = after_declare.find("\"") {
-                    return Some(after_declare[..end].to_string());
-                }
-            }
+        SyncOutcome::MissingDeclareId { program_id } => {
+            output::address("program", &program_id);
+            warning!("⚠️  No declare_id! macro found in {}", lib_path.display());
+            status!("💡 Add this line to your lib.rs:");
+            out!("   pinocchio_pubkey::declare_id!(\"{}\");", program_id);
         }
     }
-    None
-}
 
-fn read_pinoc_config() -> Result<PinocConfig> {
-    let config_path = Path::new("Pinoc.toml");
-    if !config_path.exists() {
-        anyhow::bail!("Pinoc.toml not found. Please run this command from a project root.");
-    }
-
-    let config_content =
-        fs::read_to_string(config_path).with_context(|| "Failed to read Pinoc.toml")?;
-
-    let config: PinocConfig =
-        toml::from_str(&config_content).with_context(|| "Failed to parse Pinoc.toml")?;
-
-    Ok(config)
-}
-
-fn expand_tilde(path: &str) -> Result<String> {
-    if path.starts_with("~") {
-        if let Some(home_dir) = dirs::home_dir() {
-            return Ok(path.replacen("~", home_dir.to_str().unwrap_or(""), 1));
-        } else {
-            anyhow::bail!("Could not determine the home directory to expand '~'");
-        }
-    }
-    Ok(path.to_string())
+    Ok(())
 }
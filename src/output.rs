@@ -0,0 +1,266 @@
+//! How pinoc prints: human-readable lines, or newline-delimited JSON events with `--json`.
+
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutputConfig {
+    pub verbosity: Verbosity,
+    /// print JSON events instead of human-readable lines
+    pub json: bool,
+    pub color: bool,
+    pub emoji: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        OutputConfig {
+            verbosity: Verbosity::Normal,
+            json: false,
+            color: true,
+            emoji: true,
+        }
+    }
+}
+
+static CONFIG: OnceLock<OutputConfig> = OnceLock::new();
+
+/// sets the output mode for the rest of the process; only the first call has an effect
+pub fn init(config: OutputConfig) {
+    if CONFIG.set(config).is_ok() && !config.color {
+        // child processes (cargo, solana, hooks) inherit the environment
+        std::env::set_var("NO_COLOR", "1");
+        std::env::set_var("CARGO_TERM_COLOR", "never");
+    }
+}
+
+/// the output mode set by `init`; quiet until then, so library calls don't print progress
+pub fn config() -> OutputConfig {
+    CONFIG.get().copied().unwrap_or(OutputConfig {
+        verbosity: Verbosity::Quiet,
+        ..OutputConfig::default()
+    })
+}
+
+pub fn is_json() -> bool {
+    config().json
+}
+
+pub fn color() -> bool {
+    config().color
+}
+
+/// progress messages, hidden by `--quiet` and `--json`
+pub fn status(args: fmt::Arguments) {
+    let config = config();
+    if !config.json && config.verbosity >= Verbosity::Normal {
+        print_line(&args.to_string(), config);
+    }
+}
+
+/// the results of a command (tables, reports, diffs), hidden by `--json`
+pub fn out(args: fmt::Arguments) {
+    let config = config();
+    if !config.json {
+        print_line(&args.to_string(), config);
+    }
+}
+
+/// details only shown with `--verbose`
+pub fn verbose(args: fmt::Arguments) {
+    let config = config();
+    if !config.json && config.verbosity >= Verbosity::Verbose {
+        print_line(&args.to_string(), config);
+    }
+}
+
+/// shows an external command before it runs, with `--verbose`
+pub fn command(command: &Command) {
+    let mut line = command.get_program().to_string_lossy().into_owned();
+    for arg in command.get_args() {
+        line.push(' ');
+        line.push_str(&arg.to_string_lossy());
+    }
+    verbose(format_args!("$ {}", line));
+}
+
+/// warnings go to stderr, or become a `warning` event with `--json`
+pub fn warn(args: fmt::Arguments) {
+    let config = config();
+    let message = args.to_string();
+    if config.json {
+        event(
+            "warning",
+            json!({ "message": strip_emoji(&message).trim() }),
+        );
+    } else {
+        let message = if config.emoji {
+            message
+        } else {
+            strip_emoji(&message)
+        };
+        eprintln!("{}", message);
+    }
+}
+
+/// prints a question without a newline, on stderr with `--json` so stdout stays parseable
+pub fn prompt(args: fmt::Arguments) -> io::Result<()> {
+    let config = config();
+    let question = if config.emoji {
+        args.to_string()
+    } else {
+        strip_emoji(&args.to_string())
+    };
+    if config.json {
+        let mut stderr = io::stderr().lock();
+        write!(stderr, "{}", question)?;
+        stderr.flush()
+    } else {
+        let mut stdout = io::stdout().lock();
+        write!(stdout, "{}", question)?;
+        stdout.flush()
+    }
+}
+
+/// prints `{"event": name, ...fields}` as one line with `--json`, does nothing otherwise
+pub fn event(name: &str, fields: Value) {
+    if !is_json() {
+        return;
+    }
+
+    let mut object = serde_json::Map::new();
+    object.insert("event".to_string(), Value::from(name));
+    match fields {
+        Value::Object(fields) => object.extend(fields),
+        Value::Null => {}
+        value => {
+            object.insert("data".to_string(), value);
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}", Value::Object(object));
+    let _ = stdout.flush();
+}
+
+pub fn step_started(step: &str) {
+    event("step_started", json!({ "step": step }));
+}
+
+pub fn step_finished(step: &str) {
+    event("step_finished", json!({ "step": step }));
+}
+
+pub fn step_failed(step: &str) {
+    event("step_failed", json!({ "step": step }));
+}
+
+/// a file pinoc produced or relies on, such as a keypair or the program .so
+pub fn artifact(kind: &str, path: &Path) {
+    event(
+        "artifact",
+        json!({ "kind": kind, "path": path.display().to_string() }),
+    );
+}
+
+/// an on-chain address, such as the program ID or a buffer account
+pub fn address(kind: &str, address: &str) {
+    event("address", json!({ "kind": kind, "address": address }));
+}
+
+/// where child processes write their stdout: the terminal, or stderr so they don't mix
+/// with the JSON events
+pub fn child_stdout() -> Stdio {
+    if is_json() {
+        Stdio::from(io::stderr())
+    } else {
+        Stdio::inherit()
+    }
+}
+
+fn print_line(line: &str, config: OutputConfig) {
+    if config.emoji {
+        println!("{}", line);
+    } else {
+        println!("{}", strip_emoji(line));
+    }
+}
+
+/// removes emoji and the space after them, keeping the indentation of the line
+fn strip_emoji(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_emoji(c) {
+            while chars.peek().is_some_and(|&next| is_emoji(next)) {
+                chars.next();
+            }
+            while chars.peek() == Some(&' ') {
+                chars.next();
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, transport and symbols
+        | 0x2600..=0x27BF // miscellaneous symbols and dingbats
+        | 0x2B00..=0x2BFF // arrows and stars like ⭐
+        | 0xFE0F          // emoji presentation selector
+        | 0x200D          // zero width joiner
+        | 0x2139 | 0x231A..=0x23FF // ℹ, ⏱ and friends
+    )
+}
+
+/// prints a progress line, like `println!`
+#[macro_export]
+macro_rules! status {
+    () => {
+        $crate::output::status(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::status(format_args!($($arg)*))
+    };
+}
+
+/// prints part of a command's result, like `println!`
+#[macro_export]
+macro_rules! out {
+    () => {
+        $crate::output::out(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::out(format_args!($($arg)*))
+    };
+}
+
+/// prints a line only with `--verbose`
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        $crate::output::verbose(format_args!($($arg)*))
+    };
+}
+
+/// prints a warning to stderr, like `eprintln!`
+#[macro_export]
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        $crate::output::warn(format_args!($($arg)*))
+    };
+}
@@ -0,0 +1,490 @@
+use anyhow::{Context, Result};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::keys::extract_current_program_id;
+use crate::layout::{block, parse_type, read_sources, split_fields, FieldType};
+use crate::output;
+use crate::project::Project;
+
+const MAX_SEEDS: usize = 16;
+const MAX_SEED_LEN: usize = 32;
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// where one seed of a state's `validate_pda` comes from
+#[derive(Debug, Clone)]
+pub(crate) enum SeedSource {
+    /// `Self::SEED.as_bytes()` or a byte string
+    Literal(Vec<u8>),
+    /// a parameter of `validate_pda`, like `owner: &Pubkey`
+    Param { name: String, ty: FieldType },
+}
+
+/// the seeds of a state struct, without the bump
+#[derive(Debug, Clone)]
+pub(crate) struct StateSeeds {
+    pub name: String,
+    pub seeds: Vec<SeedSource>,
+}
+
+/// `pinoc pda`: derives a program address from `--seed` values, or from the seeds `state`
+/// declares, and prints it with its bump
+pub fn show_pda(
+    state: Option<&str>,
+    seeds: &[String],
+    owner: Option<&str>,
+    program: Option<&str>,
+) -> Result<()> {
+    let project = Project::open(".")?;
+    let program_id = match program {
+        Some(program) => program.to_string(),
+        None => declared_program_id(&project)?,
+    };
+    let program_bytes = decode_pubkey(&program_id)?;
+
+    let (specs, seed_bytes) = match state {
+        Some(state) => {
+            let state_seeds = state_seeds(project.root(), state)?;
+            fill_state_seeds(&state_seeds, owner, seeds)?
+        }
+        None => {
+            if owner.is_some() {
+                anyhow::bail!("--owner needs a state, e.g. 'pinoc pda MyState --owner <address>'");
+            }
+            if seeds.is_empty() {
+                anyhow::bail!("No seeds given. Use --seed <kind:value> or a state name");
+            }
+            let bytes = seeds
+                .iter()
+                .map(|seed| parse_seed(seed))
+                .collect::<Result<Vec<_>>>()?;
+            (seeds.to_vec(), bytes)
+        }
+    };
+
+    let (address, bump) = find_program_address(&seed_bytes, &program_bytes)?;
+    let address = bs58::encode(address).into_string();
+
+    output::event(
+        "pda",
+        json!({
+            "address": address,
+            "bump": bump,
+            "program_id": program_id,
+            "seeds": specs,
+        }),
+    );
+
+    out!("📍 PDA: {}", address);
+    out!("   Bump: {}", bump);
+    out!("   Program: {}", program_id);
+    out!("   Seeds: {}", specs.join(", "));
+    Ok(())
+}
+
+/// parses a `kind:value` seed: `str:`, `pubkey:`, `hex:` or a little-endian integer like `u64:5`
+pub(crate) fn parse_seed(spec: &str) -> Result<Vec<u8>> {
+    let Some((kind, value)) = spec.split_once(':') else {
+        anyhow::bail!(
+            "Invalid seed '{}'. Use <kind:value> with str, pubkey, hex, u8-u128 or i8-i128",
+            spec
+        );
+    };
+    let invalid = || format!("Invalid {} seed '{}'", kind, value);
+
+    Ok(match kind {
+        "str" | "string" => value.as_bytes().to_vec(),
+        "pubkey" => decode_pubkey(value)?.to_vec(),
+        "hex" => decode_hex(value).with_context(invalid)?,
+        _ => match parse_type(kind) {
+            Some(FieldType::Unsigned(size)) => {
+                let value: u128 = value.replace('_', "").parse().with_context(invalid)?;
+                if size < 16 && value >> (size * 8) != 0 {
+                    anyhow::bail!("{} doesn't fit in {}", value, kind);
+                }
+                value.to_le_bytes()[..size].to_vec()
+            }
+            Some(FieldType::Signed(size)) => {
+                let value: i128 = value.replace('_', "").parse().with_context(invalid)?;
+                let bits = size as u32 * 8;
+                if size < 16 && (value < -(1 << (bits - 1)) || value >= 1 << (bits - 1)) {
+                    anyhow::bail!("{} doesn't fit in {}", value, kind);
+                }
+                value.to_le_bytes()[..size].to_vec()
+            }
+            _ => anyhow::bail!(
+                "Unknown seed kind '{}'. Use str, pubkey, hex, u8-u128 or i8-i128",
+                kind
+            ),
+        },
+    })
+}
+
+/// like `Pubkey::find_program_address`: the first bump from 255 down whose address is off the curve
+pub(crate) fn find_program_address(
+    seeds: &[Vec<u8>],
+    program_id: &[u8; 32],
+) -> Result<([u8; 32], u8)> {
+    if seeds.len() >= MAX_SEEDS {
+        anyhow::bail!("At most {} seeds are allowed with the bump", MAX_SEEDS - 1);
+    }
+    if let Some(seed) = seeds.iter().find(|seed| seed.len() > MAX_SEED_LEN) {
+        anyhow::bail!(
+            "Seeds are at most {} bytes, one is {} bytes",
+            MAX_SEED_LEN,
+            seed.len()
+        );
+    }
+
+    for bump in (0..=u8::MAX).rev() {
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(PDA_MARKER);
+        let address: [u8; 32] = hasher.finalize().into();
+
+        if CompressedEdwardsY(address).decompress().is_none() {
+            return Ok((address, bump));
+        }
+    }
+    anyhow::bail!("No bump gives an address off the curve for these seeds")
+}
+
+pub(crate) fn decode_pubkey(address: &str) -> Result<[u8; 32]> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .with_context(|| format!("Invalid address '{}'", address))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid address '{}': not 32 bytes", address))
+}
+
+/// the program ID from `declare_id!` in the program source
+pub(crate) fn declared_program_id(project: &Project) -> Result<String> {
+    let lib_path = project.lib_path();
+    let lib_content = fs::read_to_string(&lib_path)
+        .with_context(|| format!("Failed to read {}", lib_path.display()))?;
+    extract_current_program_id(&lib_content).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No declare_id! found in {}. Pass --program or run 'pinoc keys sync'",
+            lib_path.display()
+        )
+    })
+}
+
+/// reads `SEED` and the seed list of `validate_pda` from the `impl` of `state` in `src/states/`
+pub(crate) fn state_seeds(root: &Path, state: &str) -> Result<StateSeeds> {
+    let states_dir = root.join("src").join("states");
+    for source in read_sources(&states_dir)? {
+        let Some(body) = block(&source, &format!("impl {} {{", state)) else {
+            continue;
+        };
+        let seed_const = const_str(body, "SEED");
+        let Some((params, seed_list)) = validate_pda(body) else {
+            anyhow::bail!(
+                "{} has no validate_pda(...) with a seed list to derive its address from",
+                state
+            );
+        };
+
+        let seeds = split_fields(seed_list)
+            .into_iter()
+            .filter(|seed| !seed.contains("bump"))
+            .map(|seed| seed_source(seed, &params, seed_const.as_deref(), state))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(StateSeeds {
+            name: state.to_string(),
+            seeds,
+        });
+    }
+
+    anyhow::bail!("No impl {} found in {}", state, states_dir.display())
+}
+
+/// the seeds of a state from `--owner` (its first pubkey parameter) and `--seed` values (the
+/// other parameters, in order), with their `kind:value` labels
+pub(crate) fn fill_state_seeds(
+    state_seeds: &StateSeeds,
+    owner: Option<&str>,
+    seeds: &[String],
+) -> Result<(Vec<String>, Vec<Vec<u8>>)> {
+    let mut owner = owner;
+    // only the first pubkey parameter takes --owner
+    let mut owner_slot = true;
+    let mut extra = seeds.iter();
+    let mut specs = Vec::new();
+    let mut bytes = Vec::new();
+
+    for source in &state_seeds.seeds {
+        match source {
+            SeedSource::Literal(literal) => {
+                specs.push(match std::str::from_utf8(literal) {
+                    Ok(text) => format!("str:{}", text),
+                    Err(_) => format!("hex:{}", hex(literal)),
+                });
+                bytes.push(literal.clone());
+            }
+            SeedSource::Param { name, ty } => {
+                let takes_owner = owner_slot && *ty == FieldType::Pubkey;
+                owner_slot &= !takes_owner;
+                let spec = match owner.filter(|_| takes_owner) {
+                    Some(address) => {
+                        owner = None;
+                        format!("pubkey:{}", address)
+                    }
+                    None => extra.next().cloned().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "{} needs a value for its '{}' seed ({}). Pass {}",
+                            state_seeds.name,
+                            name,
+                            type_label(ty),
+                            if takes_owner {
+                                "--owner <address>".to_string()
+                            } else {
+                                format!("--seed <kind:value> for '{}'", name)
+                            }
+                        )
+                    })?,
+                };
+                let value = parse_seed(&spec)?;
+                if let Some(expected) = seed_len(ty) {
+                    if value.len() != expected {
+                        anyhow::bail!(
+                            "The '{}' seed of {} is {} bytes, '{}' is {} bytes",
+                            name,
+                            state_seeds.name,
+                            expected,
+                            spec,
+                            value.len()
+                        );
+                    }
+                }
+                specs.push(spec);
+                bytes.push(value);
+            }
+        }
+    }
+
+    if owner.is_some() {
+        anyhow::bail!("{} has no pubkey seed for --owner", state_seeds.name);
+    }
+    if extra.next().is_some() {
+        anyhow::bail!(
+            "Too many --seed values: {} only takes {}",
+            state_seeds.name,
+            state_seeds
+                .seeds
+                .iter()
+                .filter(|source| matches!(source, SeedSource::Param { .. }))
+                .count()
+        );
+    }
+    Ok((specs, bytes))
+}
+
+fn seed_source(
+    seed: &str,
+    params: &[(String, String)],
+    seed_const: Option<&str>,
+    state: &str,
+) -> Result<SeedSource> {
+    let seed = seed.trim();
+    if seed.contains("SEED") {
+        let value = seed_const
+            .ok_or_else(|| anyhow::anyhow!("{} uses SEED but declares no SEED const", state))?;
+        return Ok(SeedSource::Literal(value.as_bytes().to_vec()));
+    }
+    if let Some(literal) = string_literal(seed) {
+        return Ok(SeedSource::Literal(literal.into_bytes()));
+    }
+
+    let name = seed
+        .trim_start_matches('&')
+        .split(['.', '['])
+        .next()
+        .unwrap_or_default()
+        .trim();
+    let ty = params
+        .iter()
+        .find(|(param, _)| param == name)
+        .and_then(|(_, ty)| parse_type(ty.trim_start_matches('&').trim()))
+        .ok_or_else(|| {
+            anyhow::anyhow!("Can't tell the type of the '{}' seed of {}", seed, state)
+        })?;
+    Ok(SeedSource::Param {
+        name: name.to_string(),
+        ty,
+    })
+}
+
+fn seed_len(ty: &FieldType) -> Option<usize> {
+    match ty {
+        FieldType::Pubkey => Some(32),
+        FieldType::Unsigned(size) | FieldType::Signed(size) => Some(*size),
+        FieldType::Array(item, len) if **item == FieldType::Unsigned(1) => Some(*len),
+        _ => None,
+    }
+}
+
+fn type_label(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Pubkey => "Pubkey".to_string(),
+        FieldType::Unsigned(size) => format!("u{}", size * 8),
+        FieldType::Signed(size) => format!("i{}", size * 8),
+        FieldType::Array(_, len) => format!("{} bytes", len),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Struct(name) => name.clone(),
+    }
+}
+
+/// the value of `const <name>: &str = "..."`
+fn const_str(body: &str, name: &str) -> Option<String> {
+    let start = body.find(&format!("const {}:", name))?;
+    let statement = &body[start..start + body[start..].find(';')?];
+    string_literal(statement.split_once('=')?.1)
+}
+
+/// the parameters of `validate_pda` and the seed list it passes to `create_program_address`
+fn validate_pda(body: &str) -> Option<(Vec<(String, String)>, &str)> {
+    let start = body.find("fn validate_pda(")? + "fn validate_pda(".len();
+    let params_end = start + body[start..].find(')')?;
+    let params = split_fields(&body[start..params_end])
+        .into_iter()
+        .filter_map(|param| {
+            let (name, ty) = param.split_once(':')?;
+            Some((name.trim().to_string(), ty.trim().to_string()))
+        })
+        .collect();
+
+    let rest = &body[params_end..];
+    let list_start = rest.find("&[")? + 2;
+    let mut depth = 1;
+    for (i, c) in rest[list_start..].char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((params, &rest[list_start..list_start + i]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// the contents of the first `"..."` in `text`
+fn string_literal(text: &str) -> Option<String> {
+    let start = text.find('"')? + 1;
+    let end = start + text[start..].find('"')?;
+    Some(text[start..end].to_string())
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let value = value.trim_start_matches("0x");
+    if !value.len().is_multiple_of(2) {
+        anyhow::bail!("odd number of hex digits");
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).map_err(Into::into))
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UPGRADEABLE_LOADER: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+    fn seeds(specs: &[&str]) -> Vec<Vec<u8>> {
+        specs.iter().map(|spec| parse_seed(spec).unwrap()).collect()
+    }
+
+    #[test]
+    fn finds_known_program_address() {
+        let program_id = decode_pubkey(UPGRADEABLE_LOADER).unwrap();
+        let (address, bump) =
+            find_program_address(&seeds(&["str:Talking", "str:Squirrels"]), &program_id).unwrap();
+
+        assert_eq!(
+            bs58::encode(address).into_string(),
+            "HTqKuCuTUMwRJV4ceegG2CwYRxub4qjpj9DEg3nz1NGF"
+        );
+        assert_eq!(bump, 255);
+    }
+
+    #[test]
+    fn found_address_is_off_curve_and_bumps_above_are_on_it() {
+        let program_id = decode_pubkey(UPGRADEABLE_LOADER).unwrap();
+        for seed in 0..32u8 {
+            let seeds = vec![vec![seed]];
+            let (address, bump) = find_program_address(&seeds, &program_id).unwrap();
+            assert!(CompressedEdwardsY(address).decompress().is_none());
+
+            for higher in (bump..=u8::MAX).skip(1) {
+                let mut hasher = Sha256::new();
+                hasher.update(&seeds[0]);
+                hasher.update([higher]);
+                hasher.update(program_id);
+                hasher.update(PDA_MARKER);
+                let candidate: [u8; 32] = hasher.finalize().into();
+                assert!(CompressedEdwardsY(candidate).decompress().is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_too_many_or_too_long_seeds() {
+        let program_id = [0; 32];
+        assert!(find_program_address(&vec![vec![1]; MAX_SEEDS], &program_id).is_err());
+        assert!(find_program_address(&vec![vec![1]; MAX_SEEDS - 1], &program_id).is_ok());
+        assert!(find_program_address(&[vec![0; MAX_SEED_LEN + 1]], &program_id).is_err());
+        assert!(find_program_address(&[vec![0; MAX_SEED_LEN]], &program_id).is_ok());
+    }
+
+    #[test]
+    fn parses_seed_kinds() {
+        assert_eq!(parse_seed("str:vault").unwrap(), b"vault");
+        assert_eq!(parse_seed("hex:00ff10").unwrap(), [0x00, 0xff, 0x10]);
+        assert_eq!(
+            parse_seed(&format!("pubkey:{}", UPGRADEABLE_LOADER)).unwrap(),
+            decode_pubkey(UPGRADEABLE_LOADER).unwrap()
+        );
+        assert_eq!(parse_seed("u16:258").unwrap(), [2, 1]);
+        assert_eq!(parse_seed("u64:1_000").unwrap(), 1000u64.to_le_bytes());
+        assert_eq!(parse_seed("i16:-2").unwrap(), [0xfe, 0xff]);
+        assert_eq!(parse_seed("u128:5").unwrap(), 5u128.to_le_bytes());
+    }
+
+    #[test]
+    fn checks_integer_seed_ranges() {
+        assert_eq!(parse_seed("u8:255").unwrap(), [255]);
+        assert!(parse_seed("u8:256").is_err());
+        assert!(parse_seed("u8:-1").is_err());
+        assert_eq!(parse_seed("i8:-128").unwrap(), [0x80]);
+        assert_eq!(parse_seed("i8:127").unwrap(), [0x7f]);
+        assert!(parse_seed("i8:128").is_err());
+        assert!(parse_seed("i8:-129").is_err());
+        assert!(parse_seed("u32:4294967296").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_seeds() {
+        assert!(parse_seed("vault").is_err());
+        assert!(parse_seed("bool:true").is_err());
+        assert!(parse_seed("hex:abc").is_err());
+        assert!(parse_seed("pubkey:not-a-key").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::error::PinocError;
+use crate::keys::{extract_current_program_id, keypair_pubkey};
+use crate::project::Project;
+
+/// lamports per byte-year, matching the default rent sysvar
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3480;
+/// rent exemption requires two years worth of rent
+const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+/// bytes of account metadata charged on top of the data length
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+/// size of the upgradeable loader `Program` account
+const PROGRAM_ACCOUNT_LEN: u64 = 36;
+/// header of the upgradeable loader `ProgramData` account
+const PROGRAMDATA_HEADER_LEN: u64 = 45;
+/// header of the upgradeable loader `Buffer` account
+const BUFFER_HEADER_LEN: u64 = 37;
+/// headroom for transaction fees while writing the buffer
+const FEE_HEADROOM_LAMPORTS: u64 = 10_000_000;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// checks that a deploy is likely to succeed before handing over to `solana program deploy`
+pub fn run_deploy_checks(cluster_url: &str, wallet_path: &str, so_path: &Path) -> Result<()> {
+    status!("🔎 Running pre-flight checks...");
+
+    let wallet_pubkey = check_wallet(wallet_path)?;
+    status!("   ✅ Wallet keypair is valid: {}", wallet_pubkey);
+
+    let program_id = check_program_id()?;
+    status!("   ✅ declare_id! matches program keypair: {}", program_id);
+
+    check_build_is_fresh(so_path)?;
+    status!("   ✅ {} is up to date", so_path.display());
+
+    let so_len = fs::metadata(so_path)
+        .with_context(|| format!("Failed to read {}", so_path.display()))?
+        .len();
+    let program_cost = rent_exempt_minimum(PROGRAMDATA_HEADER_LEN + so_len)
+        + rent_exempt_minimum(PROGRAM_ACCOUNT_LEN);
+    // the buffer is refunded after the deploy but has to be funded while writing
+    let required =
+        program_cost + rent_exempt_minimum(BUFFER_HEADER_LEN + so_len) + FEE_HEADROOM_LAMPORTS;
+
+    let balance = wallet_balance(cluster_url, &wallet_pubkey)?;
+
+    status!("   💰 Program size: {} bytes", so_len);
+    status!(
+        "   💰 Estimated rent for program accounts: {:.4} SOL",
+        to_sol(program_cost)
+    );
+    status!(
+        "   💰 Required while deploying (incl. buffer and fees): {:.4} SOL",
+        to_sol(required)
+    );
+    status!("   💰 Wallet balance: {:.4} SOL", to_sol(balance));
+
+    if balance < required {
+        anyhow::bail!(
+            "Insufficient funds: wallet {} has {:.4} SOL but the deploy needs about {:.4} SOL.\n\
+            💡 Fund it with: solana airdrop {:.0} {} --url {} (devnet/localhost) or transfer SOL to it.\n\
+            💡 Use --skip-checks to deploy anyway.",
+            wallet_pubkey,
+            to_sol(balance),
+            to_sol(required),
+            to_sol(required - balance).ceil(),
+            wallet_pubkey,
+            cluster_url
+        );
+    }
+
+    status!("✅ Pre-flight checks passed");
+
+    Ok(())
+}
+
+/// rent-exempt minimum for an account holding `data_len` bytes
+fn rent_exempt_minimum(data_len: u64) -> u64 {
+    (ACCOUNT_STORAGE_OVERHEAD + data_len) * LAMPORTS_PER_BYTE_YEAR * EXEMPTION_THRESHOLD_YEARS
+}
+
+fn to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL
+}
+
+pub(crate) fn check_wallet(wallet_path: &str) -> Result<String> {
+    let path = Path::new(wallet_path);
+    let invalid = |reason: String| PinocError::InvalidWallet {
+        path: wallet_path.to_string(),
+        reason,
+    };
+    if !path.exists() {
+        return Err(invalid(format!("Wallet keypair not found: {}", wallet_path)).into());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wallet keypair: {}", wallet_path))?;
+    let bytes: Vec<u8> = serde_json::from_str(&content).map_err(|_| {
+        invalid(format!(
+            "Wallet {} is not a valid keypair file (expected a JSON array of 64 bytes).",
+            wallet_path
+        ))
+    })?;
+    if bytes.len() != 64 {
+        return Err(invalid(format!(
+            "Wallet {} is not a valid keypair file (expected 64 bytes, found {}).",
+            wallet_path,
+            bytes.len()
+        ))
+        .into());
+    }
+
+    Ok(keypair_pubkey(path)?)
+}
+
+fn check_program_id() -> Result<String> {
+    let project = Project::open(".")?;
+    let keypair_path = project.keypair_path();
+    if !keypair_path.exists() {
+        return Err(PinocError::KeypairNotFound { path: keypair_path }.into());
+    }
+    let keypair_id = keypair_pubkey(&keypair_path)?;
+
+    let lib_path = project.lib_path();
+    let lib_content = fs::read_to_string(&lib_path)
+        .with_context(|| format!("Failed to read {}", lib_path.display()))?;
+    let declared_id = extract_current_program_id(&lib_content).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No declare_id! found in {}\n💡 Run 'pinoc keys sync' to see how to add it.",
+            lib_path.display()
+        )
+    })?;
+
+    if declared_id != keypair_id {
+        return Err(PinocError::KeypairMismatch {
+            declared: declared_id,
+            keypair: keypair_id,
+        }
+        .into());
+    }
+
+    Ok(keypair_id)
+}
+
+fn check_build_is_fresh(so_path: &Path) -> Result<()> {
+    let so_modified = fs::metadata(so_path)
+        .and_then(|metadata| metadata.modified())
+        .with_context(|| format!("Failed to read {}", so_path.display()))?;
+
+    let mut newest_source = latest_modification(Path::new("src"))?;
+    if let Ok(modified) = fs::metadata("Cargo.toml").and_then(|metadata| metadata.modified()) {
+        newest_source = newest_source.max(Some(modified));
+    }
+
+    if let Some(source_modified) = newest_source {
+        if source_modified > so_modified {
+            anyhow::bail!(
+                "{} is older than the program sources.\n💡 Run 'pinoc build' before deploying.",
+                so_path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn latest_modification(dir: &Path) -> Result<Option<SystemTime>> {
+    let mut latest = None;
+    if !dir.exists() {
+        return Ok(latest);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let modified = if path.is_dir() {
+            latest_modification(&path)?
+        } else {
+            Some(fs::metadata(&path)?.modified()?)
+        };
+        latest = latest.max(modified);
+    }
+
+    Ok(latest)
+}
+
+fn wallet_balance(cluster_url: &str, wallet_pubkey: &str) -> Result<u64> {
+    let output = Command::new("solana")
+        .arg("balance")
+        .arg(wallet_pubkey)
+        .arg("--lamports")
+        .arg("--url")
+        .arg(cluster_url)
+        .output()
+        .map_err(PinocError::tool("solana"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to fetch wallet balance from {}: {}\n\
+            💡 Check that the cluster is reachable (for localhost, start 'solana-test-validator').",
+            cluster_url,
+            error.trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_whitespace()
+        .next()
+        .and_then(|lamports| lamports.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Unexpected 'solana balance' output: {}", stdout.trim()))
+}
@@ -0,0 +1,547 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::sync::OnceLock;
+
+use crate::config::{self, PinocConfig};
+use crate::error::{PinocError, Result};
+use crate::keys;
+use crate::registry::{self, TemplateOptions};
+use crate::upgrade::BUFFER_KEYPAIR_SUFFIX;
+use crate::{compat, output, wizard};
+
+/// a pinocchio program crate
+#[derive(Debug, Clone)]
+pub struct Project {
+    root: PathBuf,
+    name: String,
+    lib_name: String,
+    lib_path: PathBuf,
+    /// resolved from `cargo metadata` on first use
+    target_dir: OnceLock<PathBuf>,
+}
+
+/// how `Project::init` generates a project
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    pub name: String,
+    /// the project is created in `<directory>/<name>`
+    pub directory: PathBuf,
+    /// a built-in template name, git URL or path
+    pub template: String,
+    pub template_options: TemplateOptions,
+    pub git: bool,
+    /// run the post-init hooks of git and local templates without asking
+    pub trust_hooks: bool,
+}
+
+impl InitOptions {
+    pub fn new(name: impl Into<String>) -> Self {
+        InitOptions {
+            name: name.into(),
+            directory: PathBuf::from("."),
+            template: registry::DEFAULT_TEMPLATE.to_string(),
+            template_options: TemplateOptions::default(),
+            git: true,
+            trust_hooks: false,
+        }
+    }
+}
+
+impl Project {
+    /// opens the project containing `path`, walking up to the closest Pinoc.toml
+    pub fn discover(path: impl AsRef<Path>) -> Result<Self> {
+        Project::open(find_root(path.as_ref())?)
+    }
+
+    /// opens the program crate whose Cargo.toml is in `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let root = path.as_ref().to_path_buf();
+        let cargo_toml = root.join("Cargo.toml");
+        if !cargo_toml.exists() {
+            return Err(PinocError::NotAProject {
+                dir: root,
+                file: "Cargo.toml",
+            });
+        }
+
+        let content = fs::read_to_string(&cargo_toml).map_err(PinocError::io(&cargo_toml))?;
+        let manifest: toml::Table =
+            toml::from_str(&content).map_err(|error| PinocError::InvalidConfig {
+                path: cargo_toml.clone(),
+                message: error.message().to_string(),
+            })?;
+
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| PinocError::InvalidConfig {
+                path: cargo_toml.clone(),
+                message: "no [package] name, run this in the program crate".to_string(),
+            })?
+            .to_string();
+        let lib = manifest.get("lib");
+        // cargo build-sbf names the keypair and .so after the library target
+        let lib_name = lib
+            .and_then(|lib| lib.get("name"))
+            .and_then(|name| name.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| lib_name(&name));
+        let lib_path = root.join(
+            lib.and_then(|lib| lib.get("path"))
+                .and_then(|path| path.as_str())
+                .unwrap_or("src/lib.rs"),
+        );
+
+        Ok(Project {
+            root,
+            name,
+            lib_name,
+            lib_path,
+            target_dir: OnceLock::new(),
+        })
+    }
+
+    /// generates a new project from a template and returns it
+    pub fn init(options: InitOptions) -> Result<Self> {
+        validate_project_name(&options.name)?;
+
+        // resolve the template first so a bad name or URL fails before anything is created
+        let template = registry::resolve_template(&options.template)?;
+        registry::check_pinocchio_version(&template, &options.template_options.pinocchio_version)?;
+
+        let project_name = options.name.as_str();
+        let project_dir = options.directory.join(project_name);
+        fs::create_dir_all(&project_dir).map_err(PinocError::io(&project_dir))?;
+
+        // init new cargo project inside
+        let mut cargo_init = Command::new("cargo");
+        cargo_init
+            .arg("init")
+            .arg("--lib")
+            .arg("--name")
+            .arg(project_name);
+
+        if !options.git {
+            cargo_init.arg("--vcs").arg("none");
+        }
+
+        let output = cargo_init
+            .current_dir(&project_dir)
+            .output()
+            .map_err(PinocError::tool("cargo"))?;
+
+        if !output.status.success() {
+            return Err(PinocError::CommandFailed {
+                command: "cargo init".to_string(),
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        // the keypair goes where cargo build-sbf looks for it, which can be outside the project
+        let project = Project::open(&project_dir)?;
+        let deploy_dir = project.deploy_dir();
+        fs::create_dir_all(&deploy_dir).map_err(PinocError::io(&deploy_dir))?;
+
+        // generate keypair
+        let keypair_path = project.keypair_path();
+        let keygen_output = Command::new("solana-keygen")
+            .arg("new")
+            .arg("-o")
+            .arg(&keypair_path)
+            .arg("--no-bip39-passphrase") // skip the passphrase prompt
+            .output()
+            .map_err(PinocError::tool("solana-keygen"))?;
+
+        if !keygen_output.status.success() {
+            return Err(PinocError::CommandFailed {
+                command: "solana-keygen new".to_string(),
+                message: String::from_utf8_lossy(&keygen_output.stderr)
+                    .trim()
+                    .to_string(),
+            });
+        }
+
+        let program_address = keys::keypair_pubkey(&keypair_path)?;
+
+        let user_address_output = Command::new("solana")
+            .arg("address")
+            .current_dir(&project_dir)
+            .output()
+            .map_err(PinocError::tool("solana"))?;
+
+        let user_address = if user_address_output.status.success() {
+            String::from_utf8_lossy(&user_address_output.stdout)
+                .trim()
+                .to_string()
+        } else {
+            let error = String::from_utf8_lossy(&user_address_output.stderr);
+            warning!("⚠️  Failed to get user Solana address: {}", error);
+            String::new()
+        };
+
+        let context = registry::TemplateContext {
+            project_name,
+            program_address: &program_address,
+            user_address: &user_address,
+            options: options.template_options.clone(),
+            trust_hooks: options.trust_hooks,
+        };
+        registry::apply_template(&template, &project_dir, &context)?;
+        compat::apply_release(
+            &project_dir,
+            compat::find_release(&context.options.pinocchio_version)?,
+        )?;
+        wizard::record_choices(&project_dir, &options.template, &context.options)?;
+
+        if options.git {
+            init_git_repo(&project_dir, project_name)?;
+        }
+
+        Project::open(project_dir)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// the package name from Cargo.toml
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// the library name, which the .so and keypair files are named after
+    pub fn lib_name(&self) -> &str {
+        &self.lib_name
+    }
+
+    /// the program source holding `declare_id!`
+    pub fn lib_path(&self) -> PathBuf {
+        self.lib_path.clone()
+    }
+
+    pub fn config(&self) -> Result<PinocConfig> {
+        config::load(&self.root)
+    }
+
+    /// the target directory cargo builds into, which `CARGO_TARGET_DIR` or a workspace
+    /// can move out of the project
+    pub fn target_dir(&self) -> PathBuf {
+        self.target_dir
+            .get_or_init(|| {
+                cargo_target_dir(&self.root).unwrap_or_else(|| self.root.join("target"))
+            })
+            .clone()
+    }
+
+    /// where `cargo build-sbf` writes the program and looks for its keypair
+    pub fn deploy_dir(&self) -> PathBuf {
+        self.target_dir().join("deploy")
+    }
+
+    /// the program keypair generated by `pinoc init` or `cargo build-sbf`
+    pub fn keypair_path(&self) -> PathBuf {
+        self.deploy_dir()
+            .join(format!("{}-keypair.json", self.lib_name))
+    }
+
+    /// the program address from the program keypair
+    pub fn program_id(&self) -> Result<String> {
+        let keypair_path = self.keypair_path();
+        if !keypair_path.exists() {
+            return Err(PinocError::KeypairNotFound { path: keypair_path });
+        }
+        keys::keypair_pubkey(&keypair_path)
+    }
+
+    /// the program built by `cargo build-sbf`
+    pub fn program_so_path(&self) -> PathBuf {
+        self.deploy_dir().join(format!("{}.so", self.lib_name))
+    }
+
+    /// the keypair of the buffer `pinoc upgrade` writes the program to
+    pub fn buffer_keypair_path(&self) -> PathBuf {
+        self.deploy_dir()
+            .join(format!("{}{}", self.lib_name, BUFFER_KEYPAIR_SUFFIX))
+    }
+
+    /// the built program, or the only .so in the deploy directory when it's named differently
+    pub(crate) fn find_program_so(&self) -> anyhow::Result<PathBuf> {
+        let program_so = self.program_so_path();
+        if program_so.exists() {
+            return Ok(program_so);
+        }
+
+        let deploy_dir = self.deploy_dir();
+        if !deploy_dir.exists() {
+            anyhow::bail!(
+                "{} not found. Please run 'pinoc build' first.",
+                deploy_dir.display()
+            );
+        }
+
+        for entry in fs::read_dir(&deploy_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("so") {
+                return Ok(path);
+            }
+        }
+
+        anyhow::bail!(
+            "No .so file found in {}. Please run 'pinoc build' first.",
+            deploy_dir.display()
+        )
+    }
+
+    /// builds the program with `cargo build-sbf`
+    pub fn build(&self) -> Result<()> {
+        let status = self.run_cargo("build-sbf", &[])?;
+        if !status.success() {
+            return Err(PinocError::BuildFailed {
+                message: format!("exit code: {:?}", status.code()),
+            });
+        }
+
+        let program_so = self.program_so_path();
+        if program_so.exists() {
+            output::artifact("program", &program_so);
+        }
+        output::artifact("keypair", &self.keypair_path());
+        Ok(())
+    }
+
+    /// runs the project tests with `cargo test`
+    pub fn test(&self) -> Result<()> {
+        self.run_tests(&[])
+    }
+
+    /// runs the tests whose name contains `filter`
+    pub fn test_matching(&self, filter: &str) -> Result<()> {
+        self.run_tests(&[filter])
+    }
+
+    fn run_tests(&self, args: &[&str]) -> Result<()> {
+        let status = self.run_cargo("test", args)?;
+        if !status.success() {
+            return Err(PinocError::TestFailed {
+                message: format!("exit code: {:?}", status.code()),
+            });
+        }
+        Ok(())
+    }
+
+    fn run_cargo(&self, subcommand: &str, args: &[&str]) -> Result<ExitStatus> {
+        let mut cargo = Command::new("cargo");
+        cargo.arg(subcommand).args(args).current_dir(&self.root);
+        output::command(&cargo);
+        cargo
+            .stdout(output::child_stdout())
+            .status()
+            .map_err(PinocError::tool("cargo"))
+    }
+
+    /// removes the target directory, keeping the keypairs in the deploy directory unless
+    /// `preserve_keypairs` is false. Returns the names of the preserved keypairs.
+    pub fn clean(&self, preserve_keypairs: bool) -> Result<Vec<String>> {
+        let target_dir = self.target_dir();
+        let deploy_dir = self.deploy_dir();
+        let mut preserved_keypairs = Vec::new();
+        if !target_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        if preserve_keypairs && deploy_dir.exists() {
+            for entry in fs::read_dir(&deploy_dir).map_err(PinocError::io(&deploy_dir))? {
+                let path = entry.map_err(PinocError::io(&deploy_dir))?.path();
+                let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                if file_name.ends_with("-keypair.json") {
+                    let keypair_content = fs::read(&path).map_err(PinocError::io(&path))?;
+                    preserved_keypairs.push((file_name.to_string(), keypair_content));
+                }
+            }
+        }
+
+        fs::remove_dir_all(&target_dir).map_err(PinocError::io(&target_dir))?;
+
+        if preserve_keypairs {
+            fs::create_dir_all(&deploy_dir).map_err(PinocError::io(&deploy_dir))?;
+
+            for (keypair_name, keypair_content) in &preserved_keypairs {
+                let new_path = deploy_dir.join(keypair_name);
+                fs::write(&new_path, keypair_content).map_err(PinocError::io(&new_path))?;
+            }
+        }
+
+        Ok(preserved_keypairs
+            .into_iter()
+            .map(|(keypair_name, _)| keypair_name)
+            .collect())
+    }
+}
+
+/// crate names cargo refuses or that would shadow a dependency of the generated project
+const RESERVED_CRATE_NAMES: [&str; 18] = [
+    "std",
+    "core",
+    "alloc",
+    "proc_macro",
+    "test",
+    "build",
+    "deps",
+    "examples",
+    "incremental",
+    "pinocchio",
+    "pinocchio_pubkey",
+    "pinocchio_system",
+    "pinocchio_log",
+    "pinocchio_token",
+    "shank",
+    "solana_sdk",
+    "mollusk_svm",
+    "litesvm",
+];
+
+/// validates that the project name is a usable cargo package name: letters, numbers,
+/// underscores and hyphens, starting with a letter
+pub fn validate_project_name(name: &str) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(PinocError::InvalidProjectName {
+            name: name.to_string(),
+            reason,
+        })
+    };
+
+    let valid_chars = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !valid_chars {
+        return invalid(
+            "Project names must start with a letter and can only contain letters, numbers, \
+            underscores (_) and hyphens (-)."
+                .to_string(),
+        );
+    }
+
+    let lib_name = lib_name(name);
+    if wizard::RUST_KEYWORDS.contains(&lib_name.as_str()) {
+        return invalid(format!(
+            "'{}' is a Rust keyword and can't be used as a crate name.",
+            lib_name
+        ));
+    }
+    if RESERVED_CRATE_NAMES.contains(&lib_name.as_str()) {
+        return invalid(format!(
+            "It collides with the '{}' crate, pick another name.",
+            lib_name
+        ));
+    }
+
+    Ok(())
+}
+
+/// the library name cargo derives from a package name, used for the .so and keypair files
+pub fn lib_name(project_name: &str) -> String {
+    project_name.replace('-', "_")
+}
+
+fn init_git_repo(project_dir: &Path, project_name: &str) -> Result<()> {
+    let git_init_output = Command::new("git")
+        .arg("init")
+        .current_dir(project_dir)
+        .output()
+        .map_err(PinocError::tool("git"))?;
+
+    if !git_init_output.status.success() {
+        let error = String::from_utf8_lossy(&git_init_output.stderr);
+        warning!("⚠️  Failed to initialize git repository: {}", error);
+        return Ok(());
+    }
+
+    let git_add_output = Command::new("git")
+        .arg("add")
+        .arg(".")
+        .current_dir(project_dir)
+        .output()
+        .map_err(PinocError::tool("git"))?;
+
+    if !git_add_output.status.success() {
+        let error = String::from_utf8_lossy(&git_add_output.stderr);
+        warning!("⚠️  Failed to add files to git: {}", error);
+        return Ok(());
+    }
+
+    let commit_message = format!("Initial commit: Setup Pinocchio project '{}'", project_name);
+    let git_commit_output = Command::new("git")
+        .arg("commit")
+        .arg("-m")
+        .arg(&commit_message)
+        .current_dir(project_dir)
+        .output()
+        .map_err(PinocError::tool("git"))?;
+
+    if !git_commit_output.status.success() {
+        let error = String::from_utf8_lossy(&git_commit_output.stderr);
+        warning!("⚠️  Failed to make initial commit: {}", error);
+        // Check if it's because of missing git config
+        if error.contains("user.email") || error.contains("user.name") {
+            warning!("💡 Set your git config with:");
+            warning!("  git config --global user.email \"you@example.com\"");
+            warning!("  git config --global user.name \"Your Name\"");
+        }
+        return Ok(());
+    }
+    Ok(())
+}
+
+/// finds the project root above `start`: the closest directory with a Pinoc.toml, like cargo
+/// looks for Cargo.toml. Crates without a Pinoc.toml are found by their Cargo.toml.
+pub fn find_root(start: &Path) -> Result<PathBuf> {
+    let start = start.canonicalize().map_err(PinocError::io(start))?;
+
+    if let Some(root) = start
+        .ancestors()
+        .find(|dir| dir.join(config::CONFIG_FILE).is_file())
+    {
+        return Ok(root.to_path_buf());
+    }
+    if let Some(root) = start
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+    {
+        return Ok(root.to_path_buf());
+    }
+
+    Err(PinocError::NotAProject {
+        dir: start,
+        file: config::CONFIG_FILE,
+    })
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    target_directory: PathBuf,
+}
+
+/// the `target_directory` reported by `cargo metadata`, `None` when cargo can't tell
+fn cargo_target_dir(root: &Path) -> Option<PathBuf> {
+    let output = Command::new("cargo")
+        .arg("metadata")
+        .arg("--no-deps")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let metadata: Metadata = serde_json::from_slice(&output.stdout).ok()?;
+    Some(metadata.target_directory)
+}
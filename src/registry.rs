@@ -0,0 +1,595 @@
+use anyhow::{Context, Result};
+use include_dir::{include_dir, Dir};
+use minijinja::{Environment, Value};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use crate::error::PinocError;
+use crate::output;
+
+/// built-in templates shipped inside the binary
+static BUILTIN_TEMPLATES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+/// manifest every template directory has to contain
+pub const MANIFEST_FILE: &str = "pinoc-template.toml";
+
+/// template used by `pinoc init` when no template is given
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// `pinoc-template.toml`
+#[derive(Debug, Deserialize)]
+pub struct TemplateManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// files to copy, relative to the template directory; all files when empty
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// extra variables and their values, used as `{{ name }}` in template files
+    #[serde(default)]
+    pub placeholders: BTreeMap<String, String>,
+    /// files or directories only generated when the expression is true
+    #[serde(default)]
+    pub conditions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub hooks: TemplateHooks,
+    /// pinocchio release series (`0.9`) the sources are written for; any when empty
+    #[serde(default)]
+    pub pinocchio: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TemplateHooks {
+    /// shell commands run in the new project after the files are written
+    #[serde(default)]
+    pub post_init: Vec<String>,
+}
+
+pub struct Template {
+    pub manifest: TemplateManifest,
+    files: Vec<(PathBuf, Vec<u8>)>,
+    /// shipped inside the binary, so its hooks are trusted
+    builtin: bool,
+}
+
+/// values for the built-in template variables
+pub struct TemplateContext<'a> {
+    pub project_name: &'a str,
+    pub program_address: &'a str,
+    pub user_address: &'a str,
+    pub options: TemplateOptions,
+    /// run the post-init hooks of git and local templates without asking
+    pub trust_hooks: bool,
+}
+
+/// pinocchio version used by templates that don't pin their own
+pub const DEFAULT_PINOCCHIO_VERSION: &str = "0.8.4";
+
+/// choices exposed to templates as variables
+#[derive(Debug, Clone)]
+pub struct TemplateOptions {
+    pub boilerplate: bool,
+    pub test_framework: TestFramework,
+    pub with_shank: bool,
+    pub with_logging: bool,
+    pub pinocchio_version: String,
+    /// snake_case name of the first instruction
+    pub instruction_name: String,
+    /// PascalCase name of the first state account
+    pub state_name: String,
+    pub cluster: String,
+}
+
+impl Default for TemplateOptions {
+    fn default() -> Self {
+        TemplateOptions {
+            boilerplate: true,
+            test_framework: TestFramework::Mollusk,
+            with_shank: true,
+            with_logging: true,
+            pinocchio_version: DEFAULT_PINOCCHIO_VERSION.to_string(),
+            instruction_name: "initialize".to_string(),
+            state_name: "MyState".to_string(),
+            cluster: "localhost".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TestFramework {
+    Mollusk,
+    Litesvm,
+    None,
+}
+
+impl TestFramework {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TestFramework::Mollusk => "mollusk",
+            TestFramework::Litesvm => "litesvm",
+            TestFramework::None => "none",
+        }
+    }
+}
+
+/// resolves a built-in template name, a local template directory or a git URL
+pub fn resolve_template(spec: &str) -> Result<Template> {
+    if let Some(dir) = BUILTIN_TEMPLATES.get_dir(spec) {
+        return load_builtin(dir);
+    }
+
+    let path = Path::new(spec);
+    if path.is_dir() {
+        return load_local(path);
+    }
+
+    if is_git_url(spec) {
+        let checkout = fetch_git_template(spec)?;
+        return load_local(&checkout);
+    }
+
+    anyhow::bail!(
+        "Template '{}' not found. Use a built-in template ({}), a template directory or a git URL.",
+        spec,
+        builtin_names().join(", ")
+    )
+}
+
+/// fails when the template's sources aren't written for the given pinocchio version
+pub fn check_pinocchio_version(template: &Template, version: &str) -> Result<()> {
+    let series = crate::compat::series_of(version);
+    let supported = &template.manifest.pinocchio;
+    if !supported.is_empty() && !supported.contains(&series) {
+        anyhow::bail!(
+            "Template '{}' is written for pinocchio {} and can't be generated for {}.",
+            template.manifest.name,
+            supported.join(", "),
+            version
+        );
+    }
+    Ok(())
+}
+
+/// writes the template into `project_dir` and runs its post-init hooks
+pub fn apply_template(
+    template: &Template,
+    project_dir: &Path,
+    context: &TemplateContext,
+) -> Result<()> {
+    status!(
+        "📦 Creating project from template '{}'...",
+        template.manifest.name
+    );
+
+    let env = template_environment();
+    let variables = template_variables(template, context);
+
+    for (relative_path, content) in &template.files {
+        if !is_enabled(&env, template, relative_path, &variables)? {
+            continue;
+        }
+
+        let name = relative_path.to_string_lossy();
+        let relative_path = render(&env, &format!("{} (path)", name), &name, &variables)?;
+        // a rendered path must stay inside the project
+        if Path::new(&relative_path)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            anyhow::bail!(
+                "Template file '{}' renders to '{}', which is outside the project",
+                name,
+                relative_path
+            );
+        }
+        let destination = project_dir.join(&relative_path);
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+
+        let content = match std::str::from_utf8(content) {
+            Ok(text) => render(&env, &name, text, &variables)?.into_bytes(),
+            Err(_) => content.clone(),
+        };
+
+        fs::write(&destination, content)
+            .with_context(|| format!("Failed to write {}", destination.display()))?;
+    }
+
+    let mut hooks = template.manifest.hooks.post_init.as_slice();
+    // hooks of git and local templates are arbitrary shell commands, so they need consent
+    if !hooks.is_empty() && !template.builtin && !context.trust_hooks && !confirm_hooks(hooks)? {
+        warning!(
+            "⚠️  Skipped the post-init hooks of template '{}'. Re-run with --trust to run them.",
+            template.manifest.name
+        );
+        hooks = &[];
+    }
+
+    for hook in hooks {
+        status!("🪝 Running post-init hook: {}", hook);
+
+        let status = shell(hook)
+            .current_dir(project_dir)
+            .stdout(output::child_stdout())
+            .spawn()?
+            .wait()
+            .with_context(|| format!("Failed to run post-init hook: {}", hook))?;
+
+        if !status.success() {
+            anyhow::bail!(
+                "Post-init hook '{}' failed with exit code: {:?}",
+                hook,
+                status.code()
+            );
+        }
+    }
+
+    status!("✅ Template '{}' applied!", template.manifest.name);
+
+    Ok(())
+}
+
+/// shows the hooks of a template that isn't built in and asks whether to run them
+fn confirm_hooks(hooks: &[String]) -> Result<bool> {
+    warning!("⚠️  This template wants to run these commands in the new project:");
+    for hook in hooks {
+        warning!("      {}", hook);
+    }
+    if !crate::wizard::is_interactive() {
+        return Ok(false);
+    }
+
+    output::prompt(format_args!("Run them? [y/N]: "))?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .with_context(|| "Failed to read confirmation")?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+pub fn list_templates() -> Result<()> {
+    out!("📚 Built-in templates:");
+    for (name, description) in builtin_templates()? {
+        out!("   {:<12} {}", name, description);
+    }
+
+    let cache_dir = template_cache_dir()?;
+    if cache_dir.exists() {
+        let mut cached = Vec::new();
+        for entry in fs::read_dir(&cache_dir)? {
+            let path = entry?.path();
+            if let Ok(template) = load_local(&path) {
+                cached.push((template.manifest.name, template.manifest.description));
+            }
+        }
+
+        if !cached.is_empty() {
+            out!("\n📥 Cached git templates ({}):", cache_dir.display());
+            for (name, description) in cached {
+                out!("   {:<12} {}", name, description);
+            }
+        }
+    }
+
+    status!("\n💡 Use one with: pinoc init <project_name> --template <name|git-url|path>");
+
+    Ok(())
+}
+
+/// names and descriptions of the built-in templates
+pub fn builtin_templates() -> Result<Vec<(String, String)>> {
+    BUILTIN_TEMPLATES
+        .dirs()
+        .map(|dir| {
+            let template = load_builtin(dir)?;
+            Ok((template.manifest.name, template.manifest.description))
+        })
+        .collect()
+}
+
+fn builtin_names() -> Vec<String> {
+    BUILTIN_TEMPLATES
+        .dirs()
+        .map(|dir| dir.path().display().to_string())
+        .collect()
+}
+
+fn template_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    // block tags on their own line leave no blank line behind
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env
+}
+
+fn template_variables(template: &Template, context: &TemplateContext) -> BTreeMap<String, Value> {
+    let mut variables: BTreeMap<String, Value> = template
+        .manifest
+        .placeholders
+        .iter()
+        .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+        .collect();
+
+    let options = &context.options;
+    variables.insert("project_name".into(), context.project_name.into());
+    variables.insert(
+        "lib_name".into(),
+        crate::project::lib_name(context.project_name).into(),
+    );
+    variables.insert("program_address".into(), context.program_address.into());
+    variables.insert("user_address".into(), context.user_address.into());
+    variables.insert("boilerplate".into(), options.boilerplate.into());
+    variables.insert(
+        "with_tests".into(),
+        (options.test_framework != TestFramework::None).into(),
+    );
+    variables.insert(
+        "test_framework".into(),
+        options.test_framework.as_str().into(),
+    );
+    variables.insert("with_shank".into(), options.with_shank.into());
+    variables.insert("with_logging".into(), options.with_logging.into());
+    variables.insert(
+        "pinocchio_version".into(),
+        options.pinocchio_version.as_str().into(),
+    );
+    // `0.8`, `0.9`, `0.10`: lets templates branch on pinocchio's breaking changes
+    variables.insert(
+        "pinocchio_series".into(),
+        crate::compat::series_of(&options.pinocchio_version).into(),
+    );
+    variables.insert(
+        "instruction_name".into(),
+        options.instruction_name.as_str().into(),
+    );
+    variables.insert(
+        "instruction_type".into(),
+        pascal_case(&options.instruction_name).into(),
+    );
+    variables.insert("state_name".into(), options.state_name.as_str().into());
+    variables.insert("cluster".into(), options.cluster.as_str().into());
+
+    variables
+}
+
+fn render(
+    env: &Environment,
+    name: &str,
+    source: &str,
+    variables: &BTreeMap<String, Value>,
+) -> Result<String> {
+    env.render_named_str(name, source, variables)
+        .with_context(|| format!("Failed to render template file {}", name))
+}
+
+/// `make_offer` -> `MakeOffer`
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// evaluates the `[conditions]` matching a file, the file itself or any parent directory
+fn is_enabled(
+    env: &Environment,
+    template: &Template,
+    relative_path: &Path,
+    variables: &BTreeMap<String, Value>,
+) -> Result<bool> {
+    for (path, expression) in &template.manifest.conditions {
+        if !relative_path.starts_with(path) {
+            continue;
+        }
+
+        let enabled = env
+            .compile_expression(expression)
+            .and_then(|expression| expression.eval(variables))
+            .with_context(|| format!("Invalid condition for '{}': {}", path, expression))?;
+        if !enabled.is_true() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn parse_manifest(content: &str, source: &str) -> Result<TemplateManifest> {
+    toml::from_str(content)
+        .with_context(|| format!("Failed to parse {} of {}", MANIFEST_FILE, source))
+}
+
+fn load_builtin(dir: &Dir<'static>) -> Result<Template> {
+    let root = dir.path();
+    let manifest_content = dir
+        .get_file(root.join(MANIFEST_FILE))
+        .and_then(|file| file.contents_utf8())
+        .ok_or_else(|| anyhow::anyhow!("Built-in template {} has no manifest", root.display()))?;
+    let manifest = parse_manifest(manifest_content, &root.display().to_string())?;
+
+    let mut files = Vec::new();
+    collect_builtin_files(dir, root, &mut files);
+    let files = select_files(&manifest, files)?;
+
+    Ok(Template {
+        manifest,
+        files,
+        builtin: true,
+    })
+}
+
+fn collect_builtin_files(dir: &Dir<'static>, root: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) {
+    for file in dir.files() {
+        if let Ok(relative_path) = file.path().strip_prefix(root) {
+            files.push((relative_path.to_path_buf(), file.contents().to_vec()));
+        }
+    }
+    for subdir in dir.dirs() {
+        collect_builtin_files(subdir, root, files);
+    }
+}
+
+fn load_local(path: &Path) -> Result<Template> {
+    let manifest_path = path.join(MANIFEST_FILE);
+    let manifest_content = fs::read_to_string(&manifest_path).with_context(|| {
+        format!(
+            "Template directory {} has no {}",
+            path.display(),
+            MANIFEST_FILE
+        )
+    })?;
+    let manifest = parse_manifest(&manifest_content, &path.display().to_string())?;
+
+    let mut files = Vec::new();
+    collect_local_files(path, path, &mut files)?;
+    let files = select_files(&manifest, files)?;
+
+    Ok(Template {
+        manifest,
+        files,
+        builtin: false,
+    })
+}
+
+fn collect_local_files(dir: &Path, root: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.file_name().and_then(|name| name.to_str()) == Some(".git") {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_local_files(&path, root, files)?;
+        } else {
+            let content =
+                fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            files.push((path.strip_prefix(root)?.to_path_buf(), content));
+        }
+    }
+
+    Ok(())
+}
+
+/// keeps the files listed in the manifest (or every file) and drops the manifest itself
+fn select_files(
+    manifest: &TemplateManifest,
+    files: Vec<(PathBuf, Vec<u8>)>,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let files: Vec<_> = files
+        .into_iter()
+        .filter(|(path, _)| path != Path::new(MANIFEST_FILE))
+        .collect();
+
+    if manifest.files.is_empty() {
+        return Ok(files);
+    }
+
+    for listed in &manifest.files {
+        if !files.iter().any(|(path, _)| path == Path::new(listed)) {
+            anyhow::bail!(
+                "Template '{}' lists '{}' but the file does not exist",
+                manifest.name,
+                listed
+            );
+        }
+    }
+
+    Ok(files
+        .into_iter()
+        .filter(|(path, _)| {
+            manifest
+                .files
+                .iter()
+                .any(|listed| path == Path::new(listed))
+        })
+        .collect())
+}
+
+fn is_git_url(spec: &str) -> bool {
+    ["https://", "http://", "ssh://", "git://", "git@"]
+        .iter()
+        .any(|prefix| spec.starts_with(prefix))
+        || spec.ends_with(".git")
+}
+
+fn template_cache_dir() -> Result<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("pinoc").join("templates"))
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the cache directory"))
+}
+
+/// clones the template repository into the cache, or updates an existing checkout
+fn fetch_git_template(url: &str) -> Result<PathBuf> {
+    let cache_dir = template_cache_dir()?;
+    let checkout_name: String = url
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let checkout = cache_dir.join(checkout_name);
+
+    if checkout.exists() {
+        status!("🔄 Updating cached template {}", url);
+        let output = Command::new("git")
+            .arg("pull")
+            .arg("--ff-only")
+            .current_dir(&checkout)
+            .output()
+            .map_err(PinocError::tool("git"))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            warning!(
+                "⚠️  Failed to update template, using the cached version: {}",
+                error.trim()
+            );
+        }
+    } else {
+        status!("📥 Cloning template {}", url);
+        fs::create_dir_all(&cache_dir)
+            .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
+
+        let output = Command::new("git")
+            .arg("clone")
+            .arg("--depth")
+            .arg("1")
+            .arg(url)
+            .arg(&checkout)
+            .output()
+            .map_err(PinocError::tool("git"))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to clone template {}: {}", url, error.trim());
+        }
+    }
+
+    Ok(checkout)
+}
+
+/// runs `command` with the platform shell
+pub(crate) fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
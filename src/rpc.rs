@@ -0,0 +1,200 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// an account as returned by `getAccountInfo`
+#[derive(Debug, Clone)]
+pub struct AccountInfo {
+    pub lamports: u64,
+    pub owner: String,
+    pub executable: bool,
+    pub data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RawAccount {
+    lamports: u64,
+    owner: String,
+    executable: bool,
+    data: (String, String),
+}
+
+/// sends a JSON-RPC request to `url` and returns its `result`
+pub fn request(url: &str, method: &str, params: Value) -> Result<Value> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let response: RpcResponse = ureq::post(url)
+        .send_json(body)
+        .with_context(|| format!("Request to {} failed", url))?
+        .into_json()
+        .with_context(|| format!("Failed to parse the {} response", method))?;
+
+    if let Some(error) = response.error {
+        anyhow::bail!("{} failed: {} (code {})", method, error.message, error.code);
+    }
+    Ok(response.result.unwrap_or(Value::Null))
+}
+
+/// fetches an account, `None` when it doesn't exist
+pub fn get_account_info(url: &str, address: &str) -> Result<Option<AccountInfo>> {
+    let result = request(
+        url,
+        "getAccountInfo",
+        json!([address, { "encoding": "base64", "commitment": "confirmed" }]),
+    )?;
+
+    let Some(value) = result.get("value").filter(|value| !value.is_null()) else {
+        return Ok(None);
+    };
+    let account: RawAccount = serde_json::from_value(value.clone())
+        .with_context(|| "Failed to parse the getAccountInfo response")?;
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(&account.data.0)
+        .with_context(|| "Failed to decode the account data")?;
+
+    Ok(Some(AccountInfo {
+        lamports: account.lamports,
+        owner: account.owner,
+        executable: account.executable,
+        data,
+    }))
+}
+
+/// the outcome of a transaction: its error, if any, and the program logs
+#[derive(Debug, Clone, Default)]
+pub struct Execution {
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+}
+
+impl Execution {
+    fn from_result(result: &Value) -> Self {
+        Execution {
+            err: result.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: result
+                .get("logs")
+                .or_else(|| result.get("logMessages"))
+                .and_then(Value::as_array)
+                .map(|logs| {
+                    logs.iter()
+                        .filter_map(|log| log.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+pub fn latest_blockhash(url: &str) -> Result<[u8; 32]> {
+    let result = request(
+        url,
+        "getLatestBlockhash",
+        json!([{ "commitment": "confirmed" }]),
+    )?;
+    let blockhash = result
+        .pointer("/value/blockhash")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("getLatestBlockhash returned no blockhash"))?;
+    bs58::decode(blockhash)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("Invalid blockhash '{}'", blockhash))
+}
+
+/// runs a signed transaction without committing it
+pub fn simulate_transaction(url: &str, transaction: &[u8]) -> Result<Execution> {
+    let result = request(
+        url,
+        "simulateTransaction",
+        json!([encode(transaction), { "encoding": "base64", "commitment": "confirmed" }]),
+    )?;
+    Ok(Execution::from_result(
+        result.get("value").unwrap_or(&Value::Null),
+    ))
+}
+
+/// submits a signed transaction, returning its signature
+pub fn send_transaction(url: &str, transaction: &[u8]) -> Result<String> {
+    let result = request(
+        url,
+        "sendTransaction",
+        json!([encode(transaction), { "encoding": "base64", "skipPreflight": true }]),
+    )?;
+    result
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("sendTransaction returned no signature"))
+}
+
+/// waits until the transaction is confirmed and returns its error and logs. the error comes
+/// from the signature status, since `getTransaction` can lag behind confirmation
+pub fn confirm_transaction(url: &str, signature: &str, timeout: Duration) -> Result<Execution> {
+    let started = Instant::now();
+    let status_err = loop {
+        let result = request(
+            url,
+            "getSignatureStatuses",
+            json!([[signature], { "searchTransactionHistory": false }]),
+        )?;
+        let status = result
+            .pointer("/value/0")
+            .filter(|status| !status.is_null());
+        let confirmed = status
+            .and_then(|status| status.get("confirmationStatus"))
+            .and_then(Value::as_str)
+            .is_some_and(|status| status == "confirmed" || status == "finalized");
+        if confirmed {
+            break status
+                .and_then(|status| status.get("err"))
+                .filter(|err| !err.is_null())
+                .cloned();
+        }
+        if started.elapsed() > timeout {
+            anyhow::bail!(
+                "Transaction {} wasn't confirmed after {}s",
+                signature,
+                timeout.as_secs()
+            );
+        }
+        thread::sleep(Duration::from_millis(500));
+    };
+
+    loop {
+        let result = request(
+            url,
+            "getTransaction",
+            json!([signature, { "encoding": "json", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }]),
+        )?;
+        if let Some(meta) = result.get("meta").filter(|meta| !meta.is_null()) {
+            let mut execution = Execution::from_result(meta);
+            execution.err = status_err.or(execution.err);
+            return Ok(execution);
+        }
+        if started.elapsed() > timeout {
+            return Ok(Execution {
+                err: status_err,
+                logs: Vec::new(),
+            });
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
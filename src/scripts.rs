@@ -0,0 +1,140 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::path::Path;
+
+use crate::cluster::cluster_url;
+use crate::config::{self, expand_tilde, PinocConfig};
+use crate::error::PinocError;
+use crate::output;
+use crate::project::Project;
+use crate::registry::shell;
+
+pub const PRE_BUILD: &str = "pre-build";
+pub const POST_BUILD: &str = "post-build";
+pub const PRE_DEPLOY: &str = "pre-deploy";
+pub const POST_DEPLOY: &str = "post-deploy";
+
+/// set for the scripts pinoc runs, so a hook calling `pinoc build` doesn't run itself again
+const HOOK_ENV: &str = "PINOC_HOOK";
+
+/// `pinoc run <name>`: runs a script from `[scripts]` in Pinoc.toml, passing `args` to it
+pub fn run_script(name: &str, args: &[String]) -> Result<()> {
+    let config = config::load(Path::new("."))?;
+    let Some(script) = config.scripts.get(name) else {
+        if config.scripts.is_empty() {
+            anyhow::bail!("No script named '{}': Pinoc.toml has no [scripts]", name);
+        }
+        anyhow::bail!(
+            "No script named '{}' in Pinoc.toml. Available scripts: {}",
+            name,
+            config
+                .scripts
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+
+    let mut command = script.clone();
+    for arg in args {
+        command.push(' ');
+        command.push_str(&quote(arg));
+    }
+
+    run(name, &command, &config, None, None)
+}
+
+/// `pinoc run` without a name: lists the scripts of the project
+pub fn list_scripts() -> Result<()> {
+    let config = config::load(Path::new("."))?;
+    output::event("scripts", json!({ "scripts": &config.scripts }));
+
+    if config.scripts.is_empty() {
+        status!("❌ No scripts defined in Pinoc.toml.");
+        status!("💡 Add them under [scripts], e.g. lint = \"cargo clippy -- -D warnings\"");
+        return Ok(());
+    }
+
+    out!("📜 Scripts:");
+    for (name, script) in &config.scripts {
+        out!("   {:<16} {}", name, script);
+    }
+    Ok(())
+}
+
+/// runs the `hook` script when the project defines one, failing when it fails. `cluster` and
+/// `wallet` override the Pinoc.toml values exported to the script.
+pub fn run_hook(hook: &str, cluster: Option<&str>, wallet: Option<&str>) -> Result<()> {
+    if std::env::var_os(HOOK_ENV).is_some() {
+        return Ok(());
+    }
+
+    // crates without a Pinoc.toml have no hooks
+    let config = match config::load(Path::new(".")) {
+        Ok(config) => config,
+        Err(PinocError::NotAProject { .. }) => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+
+    match config.scripts.get(hook) {
+        Some(script) => run(hook, script, &config, cluster, wallet),
+        None => Ok(()),
+    }
+}
+
+/// runs a script with the cluster URL, wallet path and program ID in its environment
+fn run(
+    name: &str,
+    script: &str,
+    config: &PinocConfig,
+    cluster: Option<&str>,
+    wallet: Option<&str>,
+) -> Result<()> {
+    let cluster = cluster.unwrap_or(&config.provider.cluster);
+    let wallet = expand_tilde(wallet.unwrap_or(&config.provider.wallet))?;
+
+    let mut command = shell(script);
+    command
+        .env(HOOK_ENV, name)
+        .env("PINOC_CLUSTER_URL", cluster_url(cluster))
+        .env("PINOC_WALLET", &wallet);
+    // the program ID is only known once the keypair exists
+    if let Ok(program_id) = Project::open(".").and_then(|project| project.program_id()) {
+        command.env("PINOC_PROGRAM_ID", program_id);
+    }
+
+    output::step_started(name);
+    status!("📜 Running script '{}': {}", name, script);
+    let status = command
+        .stdout(output::child_stdout())
+        .status()
+        .with_context(|| format!("Failed to run script '{}'", name))?;
+
+    if !status.success() {
+        output::step_failed(name);
+        return Err(PinocError::CommandFailed {
+            command: script.to_string(),
+            message: format!("script '{}' exited with code {:?}", name, status.code()),
+        }
+        .into());
+    }
+
+    output::step_finished(name);
+    Ok(())
+}
+
+/// quotes an argument for the shell unless it only has characters the shell leaves alone
+fn quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else if cfg!(windows) {
+        format!("\"{}\"", arg)
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::output;
+
+const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
+const USER_AGENT: &str = concat!(
+    "pinoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/a91y/pinoc)"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SearchSort {
+    Downloads,
+    Recent,
+}
+
+impl SearchSort {
+    /// the `sort` parameter of the crates.io API
+    fn api_value(self) -> &'static str {
+        match self {
+            SearchSort::Downloads => "downloads",
+            SearchSort::Recent => "recent-updates",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub name: String,
+    #[serde(alias = "max_version")]
+    pub version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    pub repository: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    crates: Vec<SearchResult>,
+}
+
+/// `pinoc search`: queries crates.io, falling back to the results cached by earlier searches
+pub fn search_packages(query: Option<&str>, limit: usize, sort: Option<SearchSort>) -> Result<()> {
+    let search_term = match query {
+        Some(q) => format!("pinocchio {}", q),
+        None => "pinocchio".to_string(),
+    };
+
+    status!("🔍 Searching for packages matching '{}'...\n", search_term);
+
+    let packages = match fetch(&search_term, limit, sort) {
+        Ok(packages) => {
+            if let Err(error) = update_cache(&packages) {
+                warning!("⚠️  Could not update the search cache: {:#}", error);
+            }
+            packages
+        }
+        Err(error) => {
+            let packages = search_cache(&search_term, limit, sort)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Failed to search crates.io and no cached results are available: {:#}",
+                    error
+                )
+            })?;
+            warning!(
+                "⚠️  crates.io is unreachable ({}), showing cached results",
+                error
+            );
+            packages
+        }
+    };
+
+    output::event("search", serde_json::json!({ "results": &packages }));
+
+    if packages.is_empty() {
+        status!("No packages found matching '{}'.", search_term);
+        status!("💡 Try a different search term or check https://crates.io for more packages.");
+        return Ok(());
+    }
+
+    out!("📦 Found {} package(s):\n", packages.len());
+
+    for package in packages {
+        out!("🔹 {} v{}", package.name, package.version);
+        out!(
+            "   Description: {}",
+            package
+                .description
+                .as_deref()
+                .map(|description| description.split_whitespace().collect::<Vec<_>>().join(" "))
+                .unwrap_or_else(|| "No description available".to_string())
+        );
+        out!(
+            "   Downloads: {} · Updated: {}",
+            format_downloads(package.downloads),
+            package.updated_at.get(..10).unwrap_or(&package.updated_at)
+        );
+        if let Some(repository) = &package.repository {
+            out!("   Repository: {}", repository);
+        }
+        out!("   Install: pinoc add {}", package.name);
+        out!();
+    }
+
+    Ok(())
+}
+
+fn fetch(search_term: &str, limit: usize, sort: Option<SearchSort>) -> Result<Vec<SearchResult>> {
+    let mut request = ureq::get(CRATES_IO_API)
+        .set("User-Agent", USER_AGENT)
+        .query("q", search_term)
+        .query("per_page", &limit.to_string());
+    if let Some(sort) = sort {
+        request = request.query("sort", sort.api_value());
+    }
+
+    let response: SearchResponse = request
+        .call()
+        .with_context(|| "Request to crates.io failed")?
+        .into_json()
+        .with_context(|| "Failed to parse the crates.io response")?;
+
+    Ok(response.crates)
+}
+
+fn cache_path() -> Result<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("pinoc").join("search-index.json"))
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the cache directory"))
+}
+
+fn read_cache() -> Result<Option<BTreeMap<String, SearchResult>>> {
+    let path = cache_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let index = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(Some(index))
+}
+
+/// merges the results into the cached index, so later searches work offline
+fn update_cache(packages: &[SearchResult]) -> Result<()> {
+    let path = cache_path()?;
+    let mut index = read_cache().ok().flatten().unwrap_or_default();
+    for package in packages {
+        index.insert(package.name.clone(), package.clone());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&index)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(())
+}
+
+/// searches the cached index for crates whose name or description contains every word
+/// of the search term, `None` when nothing has been cached yet
+fn search_cache(
+    search_term: &str,
+    limit: usize,
+    sort: Option<SearchSort>,
+) -> Result<Option<Vec<SearchResult>>> {
+    let Some(index) = read_cache()? else {
+        return Ok(None);
+    };
+
+    Ok(Some(search_index(index, search_term, limit, sort)))
+}
+
+/// the crates of `index` matching the search term, best first
+fn search_index(
+    index: BTreeMap<String, SearchResult>,
+    search_term: &str,
+    limit: usize,
+    sort: Option<SearchSort>,
+) -> Vec<SearchResult> {
+    let words: Vec<String> = search_term
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let mut packages: Vec<SearchResult> = index
+        .into_values()
+        .filter(|package| {
+            let text = format!(
+                "{} {}",
+                package.name,
+                package.description.as_deref().unwrap_or_default()
+            )
+            .to_lowercase();
+            words.iter().all(|word| text.contains(word.as_str()))
+        })
+        .collect();
+
+    match sort {
+        Some(SearchSort::Recent) => packages.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
+        // without a network there is no relevance ranking, downloads come closest
+        Some(SearchSort::Downloads) | None => {
+            packages.sort_by_key(|package| Reverse(package.downloads))
+        }
+    }
+    packages.truncate(limit);
+
+    packages
+}
+
+/// 1234567 → 1,234,567
+fn format_downloads(downloads: u64) -> String {
+    let digits = downloads.to_string();
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, description: &str, downloads: u64, updated_at: &str) -> SearchResult {
+        SearchResult {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            description: Some(description.to_string()),
+            downloads,
+            repository: None,
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    fn index() -> BTreeMap<String, SearchResult> {
+        [
+            package(
+                "pinocchio",
+                "Create Solana programs with no dependencies",
+                900,
+                "2025-01-01",
+            ),
+            package(
+                "pinocchio-token",
+                "Pinocchio helpers to invoke Token program",
+                500,
+                "2025-03-01",
+            ),
+            package(
+                "pinocchio-log",
+                "Lightweight LOG helpers",
+                700,
+                "2025-02-01",
+            ),
+            package("serde", "A serialization framework", 10_000, "2025-04-01"),
+        ]
+        .into_iter()
+        .map(|package| (package.name.clone(), package))
+        .collect()
+    }
+
+    fn names(packages: &[SearchResult]) -> Vec<&str> {
+        packages
+            .iter()
+            .map(|package| package.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn search_index_matches_every_word_in_name_or_description() {
+        let packages = search_index(index(), "pinocchio TOKEN", 10, None);
+        assert_eq!(names(&packages), ["pinocchio-token"]);
+
+        let packages = search_index(index(), "pinocchio log", 10, None);
+        assert_eq!(names(&packages), ["pinocchio-log"]);
+    }
+
+    #[test]
+    fn search_index_sorts_by_downloads_by_default() {
+        let packages = search_index(index(), "pinocchio", 10, None);
+        assert_eq!(
+            names(&packages),
+            ["pinocchio", "pinocchio-log", "pinocchio-token"]
+        );
+    }
+
+    #[test]
+    fn search_index_sorts_by_recent_updates_and_limits() {
+        let packages = search_index(index(), "pinocchio", 2, Some(SearchSort::Recent));
+        assert_eq!(names(&packages), ["pinocchio-token", "pinocchio-log"]);
+    }
+
+    #[test]
+    fn format_downloads_groups_thousands() {
+        assert_eq!(format_downloads(0), "0");
+        assert_eq!(format_downloads(999), "999");
+        assert_eq!(format_downloads(1000), "1,000");
+        assert_eq!(format_downloads(123_456), "123,456");
+        assert_eq!(format_downloads(1_234_567), "1,234,567");
+    }
+}
@@ -0,0 +1,280 @@
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signer, SigningKey};
+use std::fs;
+use std::path::Path;
+
+/// an account an instruction reads or writes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Instruction {
+    pub program_id: [u8; 32],
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// reads a keypair file written by `solana-keygen`: a JSON array of the 64 secret and public bytes
+pub(crate) fn read_keypair(path: &Path) -> Result<SigningKey> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let bytes: Vec<u8> = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a keypair file", path.display()))?;
+    let secret: [u8; 32] = bytes
+        .get(..32)
+        .and_then(|secret| secret.try_into().ok())
+        .filter(|_| bytes.len() == 64)
+        .ok_or_else(|| anyhow::anyhow!("{} is not a 64-byte keypair", path.display()))?;
+
+    let key = SigningKey::from_bytes(&secret);
+    if key.verifying_key().as_bytes() != &bytes[32..] {
+        anyhow::bail!(
+            "{} is not a valid keypair: its public key doesn't match",
+            path.display()
+        );
+    }
+    Ok(key)
+}
+
+/// compiles a legacy transaction for `instruction` paid by the first signer, and signs it.
+/// `signers` must hold a key for every signer account.
+pub(crate) fn sign(
+    instruction: &Instruction,
+    signers: &[SigningKey],
+    recent_blockhash: &[u8; 32],
+) -> Result<Vec<u8>> {
+    let payer = signers
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("A transaction needs a fee payer"))?;
+
+    // the fee payer comes first, the program last
+    let mut accounts = vec![AccountMeta {
+        pubkey: payer.verifying_key().to_bytes(),
+        is_signer: true,
+        is_writable: true,
+    }];
+    for meta in instruction.accounts.iter().cloned().chain([AccountMeta {
+        pubkey: instruction.program_id,
+        is_signer: false,
+        is_writable: false,
+    }]) {
+        match accounts
+            .iter_mut()
+            .find(|account| account.pubkey == meta.pubkey)
+        {
+            Some(account) => {
+                account.is_signer |= meta.is_signer;
+                account.is_writable |= meta.is_writable;
+            }
+            None => accounts.push(meta),
+        }
+    }
+    // stable sort: writable signers, readonly signers, writable, readonly
+    accounts.sort_by_key(|account| (!account.is_signer, !account.is_writable));
+
+    let signer_count = accounts.iter().filter(|account| account.is_signer).count();
+    let readonly_signed = accounts
+        .iter()
+        .filter(|account| account.is_signer && !account.is_writable)
+        .count();
+    let readonly_unsigned = accounts
+        .iter()
+        .filter(|account| !account.is_signer && !account.is_writable)
+        .count();
+
+    let index_of = |pubkey: &[u8; 32]| {
+        accounts
+            .iter()
+            .position(|account| &account.pubkey == pubkey)
+            .unwrap_or_default() as u8
+    };
+
+    let mut message = vec![
+        signer_count as u8,
+        readonly_signed as u8,
+        readonly_unsigned as u8,
+    ];
+    push_len(&mut message, accounts.len());
+    for account in &accounts {
+        message.extend_from_slice(&account.pubkey);
+    }
+    message.extend_from_slice(recent_blockhash);
+    push_len(&mut message, 1);
+    message.push(index_of(&instruction.program_id));
+    push_len(&mut message, instruction.accounts.len());
+    for meta in &instruction.accounts {
+        message.push(index_of(&meta.pubkey));
+    }
+    push_len(&mut message, instruction.data.len());
+    message.extend_from_slice(&instruction.data);
+
+    let mut transaction = Vec::new();
+    push_len(&mut transaction, signer_count);
+    for account in accounts.iter().take(signer_count) {
+        let key = signers
+            .iter()
+            .find(|key| key.verifying_key().as_bytes() == &account.pubkey)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No keypair to sign for {}",
+                    bs58::encode(account.pubkey).into_string()
+                )
+            })?;
+        transaction.extend_from_slice(&key.sign(&message).to_bytes());
+    }
+    transaction.extend_from_slice(&message);
+
+    Ok(transaction)
+}
+
+/// appends a compact-u16 length
+fn push_len(bytes: &mut Vec<u8>, len: usize) {
+    let mut len = len;
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn meta(pubkey: [u8; 32], is_signer: bool, is_writable: bool) -> AccountMeta {
+        AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        }
+    }
+
+    /// splits a signed transaction with one signature count byte into its signatures and message
+    fn split(transaction: &[u8]) -> (Vec<&[u8]>, &[u8]) {
+        let count = transaction[0] as usize;
+        let signatures = transaction[1..1 + 64 * count].chunks(64).collect();
+        (signatures, &transaction[1 + 64 * count..])
+    }
+
+    fn account_keys(message: &[u8]) -> Vec<[u8; 32]> {
+        let count = message[3] as usize;
+        message[4..4 + 32 * count]
+            .chunks(32)
+            .map(|key| key.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn pushes_compact_u16_lengths() {
+        let encoded = |len| {
+            let mut bytes = Vec::new();
+            push_len(&mut bytes, len);
+            bytes
+        };
+        assert_eq!(encoded(0), [0x00]);
+        assert_eq!(encoded(0x7f), [0x7f]);
+        assert_eq!(encoded(0x80), [0x80, 0x01]);
+        assert_eq!(encoded(0x3fff), [0xff, 0x7f]);
+        assert_eq!(encoded(0x4000), [0x80, 0x80, 0x01]);
+        assert_eq!(encoded(0xffff), [0xff, 0xff, 0x03]);
+    }
+
+    #[test]
+    fn orders_accounts_and_counts_them_in_the_header() {
+        let payer = key(1);
+        let mint = key(2);
+        let payer_pubkey = payer.verifying_key().to_bytes();
+        let mint_pubkey = mint.verifying_key().to_bytes();
+        let readonly = [3; 32];
+        let writable = [4; 32];
+        let program_id = [5; 32];
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                meta(readonly, false, false),
+                meta(payer_pubkey, true, false),
+                meta(writable, false, true),
+                meta(mint_pubkey, true, false),
+                meta(readonly, false, false),
+            ],
+            data: vec![7, 8],
+        };
+        let blockhash = [9; 32];
+        let transaction = sign(&instruction, &[payer, mint], &blockhash).unwrap();
+
+        let (signatures, message) = split(&transaction);
+        assert_eq!(signatures.len(), 2);
+        // two signers, the mint read-only, then the read-only account and the program
+        assert_eq!(message[..3], [2, 1, 2]);
+        assert_eq!(
+            account_keys(message),
+            [payer_pubkey, mint_pubkey, writable, readonly, program_id]
+        );
+
+        let rest = &message[4 + 32 * 5..];
+        assert_eq!(rest[..32], blockhash);
+        // one instruction: program index, account indexes, data
+        assert_eq!(rest[32..], [1, 4, 5, 3, 0, 2, 1, 3, 2, 7, 8]);
+    }
+
+    #[test]
+    fn merges_duplicate_accounts_with_the_strongest_access() {
+        let payer = key(1);
+        let payer_pubkey = payer.verifying_key().to_bytes();
+        let shared = [3; 32];
+
+        let instruction = Instruction {
+            program_id: [5; 32],
+            accounts: vec![meta(shared, false, false), meta(shared, false, true)],
+            data: Vec::new(),
+        };
+        let transaction = sign(&instruction, &[payer], &[0; 32]).unwrap();
+
+        let (_, message) = split(&transaction);
+        assert_eq!(message[..3], [1, 0, 1]);
+        assert_eq!(account_keys(message), [payer_pubkey, shared, [5; 32]]);
+    }
+
+    #[test]
+    fn signs_the_message_with_every_signer() {
+        let payer = key(1);
+        let mint = key(2);
+        let instruction = Instruction {
+            program_id: [5; 32],
+            accounts: vec![meta(mint.verifying_key().to_bytes(), true, true)],
+            data: Vec::new(),
+        };
+        let transaction = sign(&instruction, &[payer.clone(), mint.clone()], &[0; 32]).unwrap();
+
+        let (signatures, message) = split(&transaction);
+        for (signature, key) in signatures.iter().zip([payer, mint]) {
+            let signature = Signature::from_slice(signature).unwrap();
+            assert!(key.verifying_key().verify(message, &signature).is_ok());
+        }
+    }
+
+    #[test]
+    fn fails_without_a_key_for_a_signer() {
+        let instruction = Instruction {
+            program_id: [5; 32],
+            accounts: vec![meta(key(2).verifying_key().to_bytes(), true, false)],
+            data: Vec::new(),
+        };
+        assert!(sign(&instruction, &[key(1)], &[0; 32]).is_err());
+        assert!(sign(&instruction, &[], &[0; 32]).is_err());
+    }
+}
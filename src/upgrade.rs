@@ -0,0 +1,283 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::deployments::record_deployment;
+use crate::error::PinocError;
+use crate::keys::keypair_pubkey;
+use crate::output;
+use crate::project::Project;
+
+/// suffix of the keypair used for the upgrade buffer, kept next to the program keypair
+pub const BUFFER_KEYPAIR_SUFFIX: &str = "-buffer-keypair.json";
+
+/// subset of `solana program show --output json`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgramShow {
+    authority: String,
+    #[serde(default)]
+    programdata_address: Option<String>,
+    #[serde(default)]
+    data_len: Option<u64>,
+}
+
+/// subset of `solana program upgrade --output json`
+#[derive(Debug, Deserialize)]
+struct UpgradeOutput {
+    #[serde(default)]
+    signature: Option<String>,
+}
+
+pub fn upgrade_program(cluster_url: &str, wallet_path: &str, buffer: Option<&str>) -> Result<()> {
+    status!("⬆️  Upgrading program");
+
+    status!("📋 Using configuration:");
+    status!("   Cluster: {}", cluster_url);
+    status!("   Wallet: {}", wallet_path);
+
+    let project = Project::open(".")?;
+    let program_keypair = project.keypair_path();
+    if !program_keypair.exists() {
+        anyhow::bail!(
+            "Program keypair not found: {}. Please run 'pinoc build' first.",
+            program_keypair.display()
+        );
+    }
+
+    let program_id = keypair_pubkey(&program_keypair)?;
+    let wallet_pubkey = keypair_pubkey(Path::new(wallet_path))?;
+
+    output::address("program", &program_id);
+    status!("   Program ID: {}", program_id);
+
+    let program = show_program(cluster_url, &program_id)?;
+    if program.authority == "none" {
+        anyhow::bail!(
+            "Program {} is immutable (no upgrade authority) and cannot be upgraded.",
+            program_id
+        );
+    }
+
+    status!("🔐 On-chain upgrade authority: {}", program.authority);
+    if let Some(programdata_address) = &program.programdata_address {
+        status!("   Program data: {}", programdata_address);
+    }
+
+    let so_path = project.find_program_so()?;
+    let so_len = fs::metadata(&so_path)
+        .with_context(|| format!("Failed to read {}", so_path.display()))?
+        .len();
+    if let Some(data_len) = program.data_len {
+        // the upgrade would fail after paying for the buffer, so stop before writing it
+        if so_len > data_len {
+            warning!(
+                "⚠️  New program is {} bytes but the program data account holds {} bytes.",
+                so_len,
+                data_len
+            );
+            anyhow::bail!(
+                "The program data account is too small for the new program.\n💡 Extend it first: solana program extend {} {} --url {} --keypair {}",
+                program_id,
+                so_len - data_len,
+                cluster_url,
+                wallet_path
+            );
+        }
+    }
+
+    let buffer_address = match buffer {
+        Some(address) => {
+            output::address("buffer", address);
+            status!("📦 Using existing buffer: {}", address);
+            address.to_string()
+        }
+        None => write_buffer(cluster_url, wallet_path, &project, &so_path)?,
+    };
+
+    if program.authority != wallet_pubkey {
+        print_multisig_plan(
+            cluster_url,
+            &program_id,
+            &buffer_address,
+            &program.authority,
+        );
+        return Ok(());
+    }
+
+    let output = Command::new("solana")
+        .arg("program")
+        .arg("upgrade")
+        .arg(&buffer_address)
+        .arg(&program_id)
+        .arg("--upgrade-authority")
+        .arg(wallet_path)
+        .arg("--url")
+        .arg(cluster_url)
+        .arg("--keypair")
+        .arg(wallet_path)
+        .arg("--output")
+        .arg("json")
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|child| child.wait_with_output())
+        .map_err(PinocError::tool("solana"))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Upgrade failed with exit code: {:?}. The buffer {} is kept; re-run with '--buffer {}' to retry.",
+            output.status.code(),
+            buffer_address,
+            buffer_address
+        );
+    }
+
+    // the buffer account is closed by the upgrade, so its keypair is no longer useful
+    let buffer_keypair = project.buffer_keypair_path();
+    if buffer.is_none() && buffer_keypair.exists() {
+        fs::remove_file(&buffer_keypair).with_context(|| {
+            format!(
+                "Failed to remove buffer keypair {}",
+                buffer_keypair.display()
+            )
+        })?;
+    }
+
+    status!("✅ Program {} upgraded successfully!", program_id);
+
+    let signature = serde_json::from_slice::<UpgradeOutput>(&output.stdout)
+        .ok()
+        .and_then(|upgrade_output| upgrade_output.signature);
+    if let Some(signature) = &signature {
+        output::event("transaction", serde_json::json!({ "signature": signature }));
+        out!("✍️  Signature: {}", signature);
+    }
+
+    record_deployment(
+        "upgrade",
+        cluster_url,
+        &program_id,
+        &wallet_pubkey,
+        // an existing buffer may hold another build than the local one
+        buffer.is_none().then_some(so_path.as_path()),
+        signature,
+    )?;
+
+    Ok(())
+}
+
+fn show_program(cluster_url: &str, program_id: &str) -> Result<ProgramShow> {
+    let output = Command::new("solana")
+        .arg("program")
+        .arg("show")
+        .arg(program_id)
+        .arg("--url")
+        .arg(cluster_url)
+        .arg("--output")
+        .arg("json")
+        .output()
+        .map_err(PinocError::tool("solana"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Program {} not found on {}: {}\nUse 'pinoc deploy' for the first deployment.",
+            program_id,
+            cluster_url,
+            error.trim()
+        );
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .with_context(|| "Failed to parse 'solana program show' output")
+}
+
+/// writes the program to a buffer account, resuming an interrupted write when the
+/// buffer keypair from a previous attempt is still around
+fn write_buffer(
+    cluster_url: &str,
+    wallet_path: &str,
+    project: &Project,
+    so_path: &Path,
+) -> Result<String> {
+    let buffer_keypair = project.buffer_keypair_path();
+
+    if buffer_keypair.exists() {
+        status!(
+            "♻️  Resuming from existing buffer keypair: {}",
+            buffer_keypair.display()
+        );
+    } else {
+        let keygen_output = Command::new("solana-keygen")
+            .arg("new")
+            .arg("-o")
+            .arg(&buffer_keypair)
+            .arg("--no-bip39-passphrase")
+            .arg("--silent")
+            .output()
+            .map_err(PinocError::tool("solana-keygen"))?;
+
+        if !keygen_output.status.success() {
+            let error = String::from_utf8_lossy(&keygen_output.stderr);
+            anyhow::bail!("Failed to generate buffer keypair: {}", error);
+        }
+    }
+
+    let buffer_address = keypair_pubkey(&buffer_keypair)?;
+    output::address("buffer", &buffer_address);
+    status!("📦 Writing program to buffer: {}", buffer_address);
+
+    let status = Command::new("solana")
+        .arg("program")
+        .arg("write-buffer")
+        .arg(so_path)
+        .arg("--buffer")
+        .arg(&buffer_keypair)
+        .arg("--url")
+        .arg(cluster_url)
+        .arg("--keypair")
+        .arg(wallet_path)
+        .stdout(output::child_stdout())
+        .status()
+        .map_err(PinocError::tool("solana"))?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Buffer write failed with exit code: {:?}. Re-run 'pinoc upgrade' to resume writing to buffer {}.",
+            status.code(),
+            buffer_address
+        );
+    }
+
+    status!("✅ Buffer written: {}", buffer_address);
+
+    Ok(buffer_address)
+}
+
+fn print_multisig_plan(cluster_url: &str, program_id: &str, buffer_address: &str, authority: &str) {
+    status!();
+    status!("⚠️  The configured wallet is not the upgrade authority of this program.");
+    status!("📋 To finish the upgrade through the authority (e.g. a multisig):");
+    status!();
+    status!("1. Hand the buffer over to the upgrade authority:");
+    status!(
+        "   $ solana program set-buffer-authority {} --new-buffer-authority {} --url {}",
+        buffer_address,
+        authority,
+        cluster_url
+    );
+    status!();
+    status!("2. Propose an upgrade from the authority:");
+    status!("   Program: {}", program_id);
+    status!("   Buffer:  {}", buffer_address);
+    status!("   Spill:   <account receiving the buffer lamports>");
+    status!();
+    status!("💡 The buffer stays open until the upgrade is executed or it is closed with:");
+    status!(
+        "   $ solana program close {} --url {}",
+        buffer_address,
+        cluster_url
+    );
+}
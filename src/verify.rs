@@ -0,0 +1,308 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{self, ToolchainConfig};
+use crate::deployments::{git_state, sha256_hex};
+use crate::error::PinocError;
+use crate::output;
+use crate::project::Project;
+
+const BUILD_MANIFEST: &str = "build-manifest.json";
+
+/// prefix the project directory is remapped to in verifiable builds
+const REMAPPED_PROJECT_DIR: &str = "/build";
+/// prefix the cargo home is remapped to in verifiable builds
+const REMAPPED_CARGO_HOME: &str = "/cargo";
+
+/// `build-manifest.json`, describing how a verifiable build was produced
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildManifest {
+    pub program: String,
+    pub so_path: String,
+    pub so_sha256: String,
+    pub build_sbf_version: String,
+    pub platform_tools_version: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_dirty: bool,
+    pub timestamp: String,
+    pub path_prefixes: Vec<String>,
+}
+
+/// builds the program with the toolchain pinned in Pinoc.toml and records the result
+/// in `build-manifest.json`
+pub fn verifiable_build() -> Result<BuildManifest> {
+    status!("Building verifiable program");
+
+    if !Path::new("Cargo.lock").exists() {
+        anyhow::bail!(
+            "Cargo.lock not found. Verifiable builds need locked dependencies: run 'cargo generate-lockfile' and commit Cargo.lock."
+        );
+    }
+
+    let installed = installed_toolchain()?;
+    let config = config::load(Path::new("."))?;
+
+    let toolchain = match config.toolchain {
+        Some(pinned) => {
+            if pinned.build_sbf_version != installed.build_sbf_version {
+                anyhow::bail!(
+                    "cargo build-sbf {} is installed but Pinoc.toml pins {}.\n\
+                    💡 Install the pinned version with: agave-install init {}",
+                    installed.build_sbf_version,
+                    pinned.build_sbf_version,
+                    pinned.build_sbf_version
+                );
+            }
+            status!(
+                "📌 Using pinned cargo build-sbf {}",
+                pinned.build_sbf_version
+            );
+            pinned
+        }
+        None => {
+            pin_toolchain(&installed)?;
+            status!(
+                "📌 Pinned cargo build-sbf {} in Pinoc.toml",
+                installed.build_sbf_version
+            );
+            installed
+        }
+    };
+
+    let project_dir = std::env::current_dir()?;
+    let cargo_home = std::env::var("CARGO_HOME")
+        .map(std::path::PathBuf::from)
+        .ok()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the cargo home directory"))?;
+
+    let path_prefixes = vec![
+        format!("{}={}", project_dir.display(), REMAPPED_PROJECT_DIR),
+        format!("{}={}", cargo_home.display(), REMAPPED_CARGO_HOME),
+    ];
+    // CARGO_ENCODED_RUSTFLAGS separates flags with 0x1f, so paths with spaces survive,
+    // and takes precedence over RUSTFLAGS, so the user's flags are carried over
+    let mut rustflags = existing_rustflags();
+    rustflags.extend(
+        path_prefixes
+            .iter()
+            .map(|prefix| format!("--remap-path-prefix={}", prefix)),
+    );
+
+    let mut build_cmd = Command::new("cargo");
+    build_cmd
+        .arg("build-sbf")
+        .env("CARGO_ENCODED_RUSTFLAGS", rustflags.join("\x1f"));
+    if let Some(platform_tools) = &toolchain.platform_tools_version {
+        build_cmd.arg("--tools-version").arg(platform_tools);
+    }
+    build_cmd.arg("--").arg("--locked");
+
+    output::command(&build_cmd);
+    let status = build_cmd
+        .stdout(output::child_stdout())
+        .status()
+        .map_err(PinocError::tool("cargo"))?;
+
+    if !status.success() {
+        return Err(PinocError::BuildFailed {
+            message: format!("exit code: {:?}", status.code()),
+        }
+        .into());
+    }
+
+    let project = Project::open(".")?;
+    let so_path = project.find_program_so()?;
+    let so_bytes =
+        fs::read(&so_path).with_context(|| format!("Failed to read {}", so_path.display()))?;
+    let (git_commit, git_dirty) = git_state();
+
+    let manifest = BuildManifest {
+        program: project.name().to_string(),
+        // relative to the project so the manifest is the same on every machine
+        so_path: std::env::current_dir()
+            .ok()
+            .and_then(|dir| so_path.strip_prefix(dir).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| so_path.clone())
+            .display()
+            .to_string(),
+        so_sha256: sha256_hex(trim_padding(&so_bytes)),
+        build_sbf_version: toolchain.build_sbf_version,
+        platform_tools_version: toolchain.platform_tools_version,
+        git_commit,
+        git_dirty,
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        path_prefixes,
+    };
+
+    fs::write(
+        BUILD_MANIFEST,
+        serde_json::to_string_pretty(&manifest)? + "\n",
+    )
+    .with_context(|| format!("Failed to write {}", BUILD_MANIFEST))?;
+
+    output::artifact("build_manifest", Path::new(BUILD_MANIFEST));
+    status!("Build completed successfully!");
+    out!("🔒 SHA-256: {}", manifest.so_sha256);
+    status!("📝 Wrote {}", BUILD_MANIFEST);
+    if manifest.git_dirty {
+        status!("⚠️  The work tree has uncommitted changes; commit them so others can reproduce this build.");
+    }
+
+    Ok(manifest)
+}
+
+/// compares a dumped on-chain program with a verifiable build of the local sources
+pub fn verify_program(program_id: &str, so: Option<&Path>, cluster_url: &str) -> Result<()> {
+    status!("🔍 Verifying program {}", program_id);
+
+    let dump_path = match so {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let path = std::env::temp_dir().join(format!("pinoc-verify-{}.so", program_id));
+            status!("📥 Dumping program from {}", cluster_url);
+            let output = Command::new("solana")
+                .arg("program")
+                .arg("dump")
+                .arg(program_id)
+                .arg(&path)
+                .arg("--url")
+                .arg(cluster_url)
+                .output()
+                .map_err(PinocError::tool("solana"))?;
+
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Failed to dump program {}: {}", program_id, error.trim());
+            }
+            path
+        }
+    };
+
+    let dump =
+        fs::read(&dump_path).with_context(|| format!("Failed to read {}", dump_path.display()))?;
+    let onchain_hash = sha256_hex(trim_padding(&dump));
+    if so.is_none() {
+        // only the hash is needed, so drop the dump instead of leaving it in the temp dir
+        let _ = fs::remove_file(&dump_path);
+    }
+
+    let manifest = verifiable_build()?;
+
+    output::event(
+        "verify",
+        serde_json::json!({
+            "program_id": program_id,
+            "onchain_sha256": onchain_hash,
+            "local_sha256": manifest.so_sha256,
+            "matches": onchain_hash == manifest.so_sha256,
+        }),
+    );
+    out!();
+    out!("   On-chain: {}", onchain_hash);
+    out!("   Local:    {}", manifest.so_sha256);
+    out!();
+
+    if onchain_hash != manifest.so_sha256 {
+        anyhow::bail!(
+            "Program {} does not match the local build.\n\
+            💡 Check that you are on the deployed commit and that Pinoc.toml pins the toolchain it was built with.",
+            program_id
+        );
+    }
+
+    status!("✅ Program {} matches the local build", program_id);
+    if manifest.git_dirty {
+        status!("⚠️  The local build includes uncommitted changes.");
+    }
+
+    Ok(())
+}
+
+/// the rustflags set in the environment, in the order cargo reads them
+fn existing_rustflags() -> Vec<String> {
+    if let Ok(encoded) = std::env::var("CARGO_ENCODED_RUSTFLAGS") {
+        return encoded
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    std::env::var("RUSTFLAGS")
+        .map(|flags| flags.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// program data accounts are zero padded, so hashes ignore trailing zero bytes
+fn trim_padding(bytes: &[u8]) -> &[u8] {
+    let end = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |position| position + 1);
+    &bytes[..end]
+}
+
+/// reads the installed toolchain from `cargo build-sbf --version`
+pub(crate) fn installed_toolchain() -> Result<ToolchainConfig> {
+    let output = Command::new("cargo")
+        .arg("build-sbf")
+        .arg("--version")
+        .output()
+        .map_err(PinocError::tool("cargo"))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to get cargo build-sbf version: {}", error.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut build_sbf_version = None;
+    let mut platform_tools_version = None;
+
+    for line in stdout.lines() {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("solana-cargo-build-sbf"), Some(version)) => {
+                build_sbf_version = Some(version.to_string())
+            }
+            (Some("platform-tools"), Some(version)) => {
+                platform_tools_version = Some(version.to_string())
+            }
+            _ => {}
+        }
+    }
+
+    let build_sbf_version = build_sbf_version.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unexpected 'cargo build-sbf --version' output: {}",
+            stdout.trim()
+        )
+    })?;
+
+    Ok(ToolchainConfig {
+        build_sbf_version,
+        platform_tools_version,
+    })
+}
+
+/// records the toolchain in the `[toolchain]` section of Pinoc.toml
+fn pin_toolchain(toolchain: &ToolchainConfig) -> Result<()> {
+    let content = fs::read_to_string("Pinoc.toml").with_context(|| "Failed to read Pinoc.toml")?;
+    let mut document: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| "Failed to parse Pinoc.toml")?;
+
+    let mut table = toml_edit::Table::new();
+    table["build_sbf_version"] = toml_edit::value(&toolchain.build_sbf_version);
+    if let Some(platform_tools) = &toolchain.platform_tools_version {
+        table["platform_tools_version"] = toml_edit::value(platform_tools);
+    }
+    document["toolchain"] = toml_edit::Item::Table(table);
+
+    fs::write("Pinoc.toml", document.to_string()).with_context(|| "Failed to write Pinoc.toml")?;
+
+    Ok(())
+}
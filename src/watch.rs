@@ -0,0 +1,259 @@
+use anyhow::{Context, Result};
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::deploy::deploy_program;
+use crate::error::PinocError;
+use crate::output;
+use crate::project::Project;
+use crate::scripts::{self, POST_BUILD, POST_DEPLOY, PRE_BUILD, PRE_DEPLOY};
+
+/// how long the watcher waits for more changes before rebuilding
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// paths of the project that trigger a rebuild
+const WATCHED_PATHS: [&str; 3] = ["src", "tests", "Cargo.toml"];
+
+/// the cluster `--deploy` redeploys to
+const LOCALNET: &str = "localhost";
+
+/// what `pinoc build --watch` and `pinoc test --watch` run after each change
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    /// run the tests after a successful build
+    pub test: bool,
+    /// only run the tests whose name contains this
+    pub filter: Option<String>,
+    /// redeploy to localnet after a successful build and test run
+    pub deploy: bool,
+}
+
+/// rebuilds the project whenever its sources change, until the process is interrupted.
+/// Deploying uses the Pinoc.toml in the current directory.
+pub fn watch(project: &Project, options: &WatchOptions) -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer =
+        new_debouncer(DEBOUNCE, sender).with_context(|| "Failed to start the file watcher")?;
+
+    let mut watched = Vec::new();
+    for name in WATCHED_PATHS {
+        let path = project.root().join(name);
+        if path.exists() {
+            debouncer
+                .watcher()
+                .watch(&path, RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {}", path.display()))?;
+            watched.push(name);
+        }
+    }
+
+    output::event("watching", json!({ "paths": watched }));
+    status!(
+        "👀 Watching {} for changes. Press Ctrl-C to stop.",
+        watched.join(", ")
+    );
+    status!();
+
+    run_cycle(project, options);
+
+    for result in receiver {
+        let events = match result {
+            Ok(events) => events,
+            Err(error) => {
+                warning!("⚠️  File watcher error: {}", error);
+                continue;
+            }
+        };
+
+        let mut changed: Vec<PathBuf> = events
+            .into_iter()
+            .map(|event| event.path)
+            .filter(|path| is_source_change(path))
+            .collect();
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            continue;
+        }
+
+        let root = project.root().canonicalize().unwrap_or_default();
+        let changed: Vec<String> = changed
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&root)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        output::event("changed", json!({ "paths": changed }));
+        status!();
+        status!("🔄 Changed: {}", changed.join(", "));
+
+        run_cycle(project, options);
+    }
+
+    Ok(())
+}
+
+/// builds, tests and deploys once, reporting failures without stopping the watcher
+fn run_cycle(project: &Project, options: &WatchOptions) {
+    let started = Instant::now();
+
+    if !run_hook(PRE_BUILD, None)
+        || !run_step(project, "build", &["build-sbf"])
+        || !run_hook(POST_BUILD, None)
+    {
+        return;
+    }
+
+    if options.test {
+        let mut args = vec!["test"];
+        if let Some(filter) = &options.filter {
+            args.push(filter);
+        }
+        if !run_step(project, "test", &args) {
+            return;
+        }
+    }
+
+    if options.deploy {
+        if !run_hook(PRE_DEPLOY, Some(LOCALNET)) {
+            return;
+        }
+        output::step_started("deploy");
+        status!("🚀 Redeploying to {}", LOCALNET);
+        match deploy_program(Some(LOCALNET), None, false) {
+            Ok(()) => output::step_finished("deploy"),
+            Err(error) => {
+                output::step_failed("deploy");
+                warning!("❌ Deploy failed: {:#}", error);
+                return;
+            }
+        }
+        if !run_hook(POST_DEPLOY, Some(LOCALNET)) {
+            return;
+        }
+    }
+
+    status!(
+        "✅ Done in {:.1}s, waiting for changes...",
+        started.elapsed().as_secs_f64()
+    );
+}
+
+/// runs a Pinoc.toml hook like `pinoc build` and `pinoc deploy` do, reporting a failure
+/// instead of stopping the watcher
+fn run_hook(hook: &str, cluster: Option<&str>) -> bool {
+    match scripts::run_hook(hook, cluster, None) {
+        Ok(()) => true,
+        Err(error) => {
+            warning!("❌ {} hook failed: {:#}", hook, error);
+            false
+        }
+    }
+}
+
+/// runs `cargo <args>` with its output captured, printing a one-line summary on success and
+/// the output on failure
+fn run_step(project: &Project, step: &str, args: &[&str]) -> bool {
+    output::step_started(step);
+    let started = Instant::now();
+
+    let mut cargo = Command::new("cargo");
+    cargo.args(args).current_dir(project.root());
+    output::command(&cargo);
+    let result = cargo.output().map_err(PinocError::tool("cargo"));
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let output = match result {
+        Ok(output) => output,
+        Err(error) => {
+            output::step_failed(step);
+            warning!("❌ {}: {}", step, error);
+            return false;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        output::step_finished(step);
+        match (step, test_summary(&stdout)) {
+            ("test", Some(summary)) => status!("🧪 Tests passed: {} ({:.1}s)", summary, elapsed),
+            ("test", None) => status!("🧪 Tests passed ({:.1}s)", elapsed),
+            _ => status!("🔨 Build succeeded ({:.1}s)", elapsed),
+        }
+        return true;
+    }
+
+    output::step_failed(step);
+    print_failure(&output);
+    match (step, test_summary(&stdout)) {
+        ("test", Some(summary)) => warning!("❌ Tests failed: {} ({:.1}s)", summary, elapsed),
+        ("test", None) => warning!("❌ Tests failed ({:.1}s)", elapsed),
+        _ => warning!("❌ Build failed ({:.1}s)", elapsed),
+    }
+    false
+}
+
+/// prints what cargo reported, starting at the failing tests when there are any
+fn print_failure(output: &Output) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout
+        .find("\nfailures:")
+        .map_or(stdout.as_ref(), |start| &stdout[start + 1..]);
+
+    for text in [stderr.trim_end(), stdout.trim_end()] {
+        if !text.is_empty() {
+            eprintln!("{}", text);
+        }
+    }
+}
+
+/// adds up the `test result:` lines of `cargo test`, e.g. "12 passed, 1 failed"
+fn test_summary(stdout: &str) -> Option<String> {
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut found = false;
+
+    for line in stdout.lines() {
+        let Some(result) = line.trim().strip_prefix("test result:") else {
+            continue;
+        };
+        found = true;
+        for part in result.split(';') {
+            let mut words = part.split_whitespace().rev();
+            let (Some(label), Some(count)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let count: u64 = count.parse().unwrap_or(0);
+            match label {
+                "passed" => passed += count,
+                "failed" => failed += count,
+                _ => {}
+            }
+        }
+    }
+
+    found.then(|| format!("{} passed, {} failed", passed, failed))
+}
+
+/// ignores editor swap and backup files, and temporary files that are already gone
+fn is_source_change(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    if name.starts_with('.') || name.ends_with('~') || name.ends_with(".swp") {
+        return false;
+    }
+    path.exists()
+        || matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("rs" | "toml")
+        )
+}
@@ -0,0 +1,221 @@
+use anyhow::{Context, Result};
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use dialoguer::{Confirm, Input, Select};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::project::validate_project_name;
+use crate::registry::{self, TemplateOptions, TestFramework, DEFAULT_TEMPLATE};
+use crate::{compat, output};
+
+/// cluster profiles offered for `[provider] cluster`
+const CLUSTERS: [&str; 4] = ["localhost", "devnet", "testnet", "mainnet-beta"];
+
+const TEST_FRAMEWORKS: [TestFramework; 3] = [
+    TestFramework::Mollusk,
+    TestFramework::Litesvm,
+    TestFramework::None,
+];
+
+pub const RUST_KEYWORDS: [&str; 51] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+/// everything `pinoc init` needs to generate a project
+pub struct InitChoices {
+    pub project_name: String,
+    pub template: String,
+    pub options: TemplateOptions,
+}
+
+/// the wizard only runs when a person can answer it
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// asks for the project settings, using `project_name` when it was given on the command line
+pub fn run(project_name: Option<&str>) -> Result<InitChoices> {
+    let theme: Box<dyn Theme> = if output::color() {
+        Box::new(ColorfulTheme::default())
+    } else {
+        Box::new(SimpleTheme)
+    };
+    let theme = theme.as_ref();
+    let defaults = TemplateOptions::default();
+
+    status!("🧑🏻‍🍳 Let's set up your pinocchio project (pass --yes to skip these questions)");
+    status!();
+
+    let project_name = match project_name {
+        Some(name) => name.to_string(),
+        None => Input::<String>::with_theme(theme)
+            .with_prompt("Project name")
+            .validate_with(|name: &String| validate_project_name(name).map_err(|e| e.to_string()))
+            .interact_text()?,
+    };
+
+    let mut templates = registry::builtin_templates()?;
+    // the default template first, so Enter picks it
+    templates.sort_by_key(|(name, _)| name != DEFAULT_TEMPLATE);
+    let mut items: Vec<String> = templates
+        .iter()
+        .map(|(name, description)| format!("{:<12} {}", name, description))
+        .collect();
+    items.push("Other (git URL or path)".to_string());
+
+    let selection = Select::with_theme(theme)
+        .with_prompt("Template")
+        .items(&items)
+        .default(0)
+        .interact()?;
+    let template = match templates.get(selection) {
+        Some((name, _)) => name.clone(),
+        None => Input::<String>::with_theme(theme)
+            .with_prompt("Template git URL or path")
+            .interact_text()?,
+    };
+
+    let mut options = TemplateOptions::default();
+
+    // the remaining questions shape the default template only
+    if template == DEFAULT_TEMPLATE {
+        let versions: Vec<&str> = compat::RELEASES
+            .iter()
+            .map(|release| release.pinocchio)
+            .collect();
+        let selection = Select::with_theme(theme)
+            .with_prompt("Pinocchio version")
+            .items(&versions)
+            .default(
+                versions
+                    .iter()
+                    .position(|version| *version == defaults.pinocchio_version)
+                    .unwrap_or(0),
+            )
+            .interact()?;
+        options.pinocchio_version = versions[selection].to_string();
+
+        options.boilerplate = Confirm::with_theme(theme)
+            .with_prompt("Generate instruction and state boilerplate?")
+            .default(true)
+            .interact()?;
+    }
+
+    if template == DEFAULT_TEMPLATE && options.boilerplate {
+        let frameworks: Vec<&str> = TEST_FRAMEWORKS.iter().map(|f| f.as_str()).collect();
+        let selection = Select::with_theme(theme)
+            .with_prompt("Test framework")
+            .items(&frameworks)
+            .default(0)
+            .interact()?;
+        options.test_framework = TEST_FRAMEWORKS[selection];
+
+        options.with_shank = Confirm::with_theme(theme)
+            .with_prompt("Use shank for IDL generation?")
+            .default(true)
+            .interact()?;
+
+        options.with_logging = Confirm::with_theme(theme)
+            .with_prompt("Log instruction names?")
+            .default(true)
+            .interact()?;
+
+        options.instruction_name = Input::<String>::with_theme(theme)
+            .with_prompt("First instruction name (snake_case)")
+            .default(defaults.instruction_name)
+            .validate_with(|name: &String| {
+                validate_instruction_name(name).map_err(|e| e.to_string())
+            })
+            .interact_text()?;
+
+        options.state_name = Input::<String>::with_theme(theme)
+            .with_prompt("State account name (PascalCase)")
+            .default(defaults.state_name)
+            .validate_with(|name: &String| validate_state_name(name).map_err(|e| e.to_string()))
+            .interact_text()?;
+    }
+
+    let selection = Select::with_theme(theme)
+        .with_prompt("Cluster")
+        .items(&CLUSTERS)
+        .default(0)
+        .interact()?;
+    options.cluster = CLUSTERS[selection].to_string();
+
+    status!();
+
+    Ok(InitChoices {
+        project_name,
+        template,
+        options,
+    })
+}
+
+/// instruction names become a module and a function, so they must be snake_case identifiers
+pub fn validate_instruction_name(name: &str) -> Result<()> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if !valid || RUST_KEYWORDS.contains(&name) {
+        anyhow::bail!(
+            "Invalid instruction name '{}'. Use a snake_case name such as 'initialize' or 'make_offer'.",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// state names become a struct, so they must be PascalCase identifiers
+pub fn validate_state_name(name: &str) -> Result<()> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if !valid || name == "Self" {
+        anyhow::bail!(
+            "Invalid state name '{}'. Use a PascalCase name such as 'MyState' or 'Escrow'.",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// records the init choices in the `[init]` section of the project's Pinoc.toml and
+/// sets the chosen cluster
+pub fn record_choices(project_dir: &Path, template: &str, options: &TemplateOptions) -> Result<()> {
+    let path = project_dir.join("Pinoc.toml");
+    let content = if path.exists() {
+        fs::read_to_string(&path).with_context(|| "Failed to read Pinoc.toml")?
+    } else {
+        // templates without a Pinoc.toml still get a usable provider section
+        "[provider]\nwallet = \"~/.config/solana/id.json\"\n".to_string()
+    };
+    let mut document: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| "Failed to parse Pinoc.toml")?;
+
+    document["provider"]["cluster"] = toml_edit::value(&options.cluster);
+
+    let mut table = toml_edit::Table::new();
+    table["template"] = toml_edit::value(template);
+    table["pinocchio_version"] = toml_edit::value(&options.pinocchio_version);
+    table["boilerplate"] = toml_edit::value(options.boilerplate);
+    table["test_framework"] = toml_edit::value(options.test_framework.as_str());
+    table["shank"] = toml_edit::value(options.with_shank);
+    table["logging"] = toml_edit::value(options.with_logging);
+    table["instruction"] = toml_edit::value(&options.instruction_name);
+    table["state"] = toml_edit::value(&options.state_name);
+    document["init"] = toml_edit::Item::Table(table);
+
+    fs::write(&path, document.to_string()).with_context(|| "Failed to write Pinoc.toml")?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    errors::MyProgramError,
+    states::{utils::load_acc_mut_unchecked, Counter},
+};
+
+pub fn increment(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [authority_acc, counter_acc] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !counter_acc.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut data = counter_acc.try_borrow_mut_data()?;
+    let counter = unsafe { load_acc_mut_unchecked::<Counter>(&mut data)? };
+
+    if counter.authority.ne(authority_acc.key()) {
+        return Err(MyProgramError::InvalidAuthority.into());
+    }
+
+    counter.increment()
+}
@@ -0,0 +1,61 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::states::{
+    utils::{load_ix_data, DataLen},
+    Counter,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Initialize {
+    pub bump: u8,
+}
+
+impl DataLen for Initialize {
+    const LEN: usize = core::mem::size_of::<Initialize>();
+}
+
+pub fn initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority_acc, counter_acc, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !counter_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let ix_data = unsafe { load_ix_data::<Initialize>(data)? };
+
+    Counter::validate_pda(ix_data.bump, counter_acc.key(), authority_acc.key())?;
+
+    let bump_bytes = [ix_data.bump];
+    let signer_seeds = [
+        Seed::from(Counter::SEED.as_bytes()),
+        Seed::from(authority_acc.key()),
+        Seed::from(&bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    CreateAccount {
+        from: authority_acc,
+        to: counter_acc,
+        space: Counter::LEN as u64,
+        owner: &crate::ID,
+        lamports: Rent::get()?.minimum_balance(Counter::LEN),
+    }
+    .invoke_signed(&signers)?;
+
+    Counter::initialize(counter_acc, authority_acc.key(), ix_data.bump)
+}
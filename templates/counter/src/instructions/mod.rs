@@ -0,0 +1,25 @@
+use pinocchio::program_error::ProgramError;
+
+pub mod increment;
+pub mod initialize;
+
+pub use increment::*;
+pub use initialize::*;
+
+#[repr(u8)]
+pub enum ProgramInstruction {
+    Initialize,
+    Increment,
+}
+
+impl TryFrom<&u8> for ProgramInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProgramInstruction::Initialize),
+            1 => Ok(ProgramInstruction::Increment),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::utils::{load_acc_mut_unchecked, DataLen};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use crate::errors::MyProgramError;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl DataLen for Counter {
+    const LEN: usize = core::mem::size_of::<Counter>();
+}
+
+impl Counter {
+    pub const SEED: &'static str = "counter";
+
+    pub fn validate_pda(bump: u8, pda: &Pubkey, authority: &Pubkey) -> Result<(), ProgramError> {
+        let seed_with_bump = &[Self::SEED.as_bytes(), authority, &[bump]];
+        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn initialize(counter_acc: &AccountInfo, authority: &Pubkey, bump: u8) -> ProgramResult {
+        let counter =
+            unsafe { load_acc_mut_unchecked::<Counter>(counter_acc.borrow_mut_data_unchecked()) }?;
+
+        counter.authority = *authority;
+        counter.count = 0;
+        counter.bump = bump;
+        Ok(())
+    }
+
+    pub fn increment(&mut self) -> ProgramResult {
+        self.count = self
+            .count
+            .checked_add(1)
+            .ok_or(MyProgramError::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod counter;
+pub mod utils;
+
+pub use counter::*;
+pub use utils::*;
@@ -0,0 +1,78 @@
+use mollusk_svm::result::{Check, ProgramResult};
+use mollusk_svm::{program, Mollusk};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use {{ lib_name }}::states::Counter;
+use {{ lib_name }}::states::utils::DataLen;
+
+pub const PROGRAM: Pubkey = pubkey!("{{ program_address }}");
+
+pub const AUTHORITY: Pubkey = pubkey!("{{ user_address }}");
+
+pub fn mollusk() -> Mollusk {
+    Mollusk::new(&PROGRAM, "target/deploy/{{ lib_name }}")
+}
+
+fn counter_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Counter::SEED.as_bytes(), &AUTHORITY.to_bytes()], &PROGRAM)
+}
+
+#[test]
+fn test_initialize_and_increment() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (counter, bump) = counter_pda();
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let initialize = Instruction::new_with_bytes(
+        PROGRAM,
+        &[0, bump],
+        vec![
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize,
+        &[
+            (AUTHORITY, authority_account),
+            (counter, counter_account),
+            (system_program, system_account),
+        ],
+        &[Check::success()],
+    );
+    assert!(result.program_result == ProgramResult::Success);
+
+    let authority_account = result.get_account(&AUTHORITY).unwrap().clone();
+    let counter_account = result.get_account(&counter).unwrap().clone();
+    assert_eq!(counter_account.data.len(), Counter::LEN);
+
+    let increment = Instruction::new_with_bytes(
+        PROGRAM,
+        &[1],
+        vec![
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new(counter, false),
+        ],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &increment,
+        &[(AUTHORITY, authority_account), (counter, counter_account)],
+        &[Check::success()],
+    );
+    assert!(result.program_result == ProgramResult::Success);
+
+    let data = &result.get_account(&counter).unwrap().data;
+    let count = u64::from_le_bytes(data[32..40].try_into().unwrap());
+    assert_eq!(count, 1);
+}
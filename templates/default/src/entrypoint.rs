@@ -0,0 +1,61 @@
+#![allow(unexpected_cfgs)]
+
+use crate::instructions::{self, ProgramInstruction};
+{% if pinocchio_series == "0.10" %}
+use pinocchio::{
+    default_panic_handler, error::ProgramError, no_allocator, program_entrypoint, AccountView,
+    Address, ProgramResult,
+};
+{% if with_logging %}
+use pinocchio_log::log;
+{% endif %}
+{% else %}
+use pinocchio::{
+{% if with_logging %}
+    account_info::AccountInfo, default_panic_handler, msg, no_allocator, program_entrypoint,
+    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+{% else %}
+    account_info::AccountInfo, default_panic_handler, no_allocator, program_entrypoint,
+    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+{% endif %}
+};
+{% endif %}
+
+// This is the entrypoint for the program.
+program_entrypoint!(process_instruction);
+//Do not allocate memory.
+no_allocator!();
+// Use the no_std panic handler.
+default_panic_handler!();
+
+#[inline(always)]
+{% if pinocchio_series == "0.10" %}
+fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+{% else %}
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+{% endif %}
+    let (ix_disc, instruction_data) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match ProgramInstruction::try_from(ix_disc)? {
+        ProgramInstruction::{{ instruction_type }} => {
+{% if with_logging %}
+{% if pinocchio_series == "0.10" %}
+            log!("{{ instruction_name }}");
+{% else %}
+            msg!("{{ instruction_name }}");
+{% endif %}
+{% endif %}
+            instructions::{{ instruction_name }}(accounts, instruction_data)
+        }
+    }
+}
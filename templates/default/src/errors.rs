@@ -0,0 +1,22 @@
+{% if pinocchio_series == "0.10" %}
+use pinocchio::error::ProgramError;
+{% else %}
+use pinocchio::program_error::ProgramError;
+{% endif %}
+
+{% if with_shank %}
+#[derive(Clone, PartialEq, shank::ShankType)]
+{% else %}
+#[derive(Clone, PartialEq)]
+{% endif %}
+pub enum MyProgramError {
+    InvalidInstructionData,
+    PdaMismatch,
+    InvalidOwner,
+}
+
+impl From<MyProgramError> for ProgramError {
+    fn from(e: MyProgramError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
@@ -0,0 +1,25 @@
+{% if pinocchio_series == "0.10" %}
+use pinocchio::error::ProgramError;
+{% else %}
+use pinocchio::program_error::ProgramError;
+{% endif %}
+
+pub mod {{ instruction_name }};
+
+pub use {{ instruction_name }}::*;
+
+#[repr(u8)]
+pub enum ProgramInstruction {
+    {{ instruction_type }},
+}
+
+impl TryFrom<&u8> for ProgramInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProgramInstruction::{{ instruction_type }}),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
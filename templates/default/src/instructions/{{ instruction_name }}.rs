@@ -0,0 +1,153 @@
+{% if pinocchio_series == "0.10" %}
+use pinocchio::{
+    cpi::{Seed, Signer},
+    error::ProgramError,
+    sysvars::rent::Rent,
+    AccountView, Address, ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::MyProgramError,
+    states::{
+        utils::{load_ix_data, DataLen},
+        {{ state_name }},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct {{ instruction_type }} {
+    pub owner: Address,
+    pub bump: u8,
+}
+
+impl DataLen for {{ instruction_type }} {
+    const LEN: usize = core::mem::size_of::<{{ instruction_type }}>();
+}
+
+pub fn {{ instruction_name }}(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
+    let [payer_acc, state_acc, sysvar_rent_acc, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state_acc.is_data_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent = Rent::from_account_view(sysvar_rent_acc)?;
+
+    let ix_data = unsafe { load_ix_data::<{{ instruction_type }}>(data)? };
+
+    if ix_data.owner.ne(payer_acc.address()) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let pda_bump_bytes = [ix_data.bump];
+
+    {{ state_name }}::validate_pda(ix_data.bump, state_acc.address(), &ix_data.owner)?;
+
+    // signer seeds
+    let signer_seeds = [
+        Seed::from({{ state_name }}::SEED.as_bytes()),
+        Seed::from(ix_data.owner.as_ref()),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    CreateAccount {
+        from: payer_acc,
+        to: state_acc,
+        space: {{ state_name }}::LEN as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance({{ state_name }}::LEN),
+    }
+    .invoke_signed(&signers)?;
+
+    {{ state_name }}::initialize(state_acc, ix_data)?;
+
+    Ok(())
+}
+{% else %}
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::rent::Rent,
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::MyProgramError,
+    states::{
+        utils::{load_ix_data, DataLen},
+        {{ state_name }},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct {{ instruction_type }} {
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+impl DataLen for {{ instruction_type }} {
+    const LEN: usize = core::mem::size_of::<{{ instruction_type }}>();
+}
+
+pub fn {{ instruction_name }}(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer_acc, state_acc, sysvar_rent_acc, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent = Rent::from_account_info(sysvar_rent_acc)?;
+
+    let ix_data = unsafe { load_ix_data::<{{ instruction_type }}>(data)? };
+
+    if ix_data.owner.ne(payer_acc.key()) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let pda_bump_bytes = [ix_data.bump];
+
+    {{ state_name }}::validate_pda(ix_data.bump, state_acc.key(), &ix_data.owner)?;
+
+    // signer seeds
+    let signer_seeds = [
+        Seed::from({{ state_name }}::SEED.as_bytes()),
+        Seed::from(&ix_data.owner),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    CreateAccount {
+        from: payer_acc,
+        to: state_acc,
+        space: {{ state_name }}::LEN as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance({{ state_name }}::LEN),
+    }
+    .invoke_signed(&signers)?;
+
+    {{ state_name }}::initialize(state_acc, ix_data)?;
+
+    Ok(())
+}
+{% endif %}
@@ -0,0 +1,45 @@
+{% if boilerplate %}
+#![no_std]
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod errors;
+pub mod instructions;
+pub mod states;
+
+{% if pinocchio_series == "0.10" %}
+solana_address::declare_id!("{{ program_address }}");
+{% else %}
+pinocchio_pubkey::declare_id!("{{ program_address }}");
+{% endif %}
+{% elif pinocchio_series == "0.10" %}
+use pinocchio::{AccountView, Address, ProgramResult};
+
+solana_address::declare_id!("{{ program_address }}");
+
+pub fn process_instruction(
+    _program_id: &Address,
+    _accounts: &[AccountView],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // Your program logic here
+    Ok(())
+}
+{% else %}
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, ProgramResult};
+
+pinocchio_pubkey::declare_id!("{{ program_address }}");
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // Your program logic here
+    Ok(())
+}
+{% endif %}
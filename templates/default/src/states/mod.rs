@@ -0,0 +1,5 @@
+pub mod state;
+pub mod utils;
+
+pub use state::*;
+pub use utils::*;
@@ -0,0 +1,79 @@
+{% if pinocchio_series == "0.10" %}
+use super::utils::{load_acc_mut_unchecked, DataLen};
+use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+
+use crate::{errors::MyProgramError, instructions::{{ instruction_type }}};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct {{ state_name }} {
+    pub owner: Address,
+}
+
+impl DataLen for {{ state_name }} {
+    const LEN: usize = core::mem::size_of::<{{ state_name }}>();
+}
+
+impl {{ state_name }} {
+    pub const SEED: &'static str = "init";
+
+    pub fn validate_pda(bump: u8, pda: &Address, owner: &Address) -> Result<(), ProgramError> {
+        let seed_with_bump = &[Self::SEED.as_bytes(), owner.as_ref(), &[bump]];
+        let derived = Address::create_program_address(seed_with_bump, &crate::ID)
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn initialize(state_acc: &AccountView, ix_data: &{{ instruction_type }}) -> ProgramResult {
+        let state =
+            unsafe { load_acc_mut_unchecked::<{{ state_name }}>(state_acc.borrow_unchecked_mut()) }?;
+
+        state.owner = ix_data.owner;
+        Ok(())
+    }
+}
+{% else %}
+use super::utils::{load_acc_mut_unchecked, DataLen};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use crate::{errors::MyProgramError, instructions::{{ instruction_type }}};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct {{ state_name }} {
+    pub owner: Pubkey,
+}
+
+impl DataLen for {{ state_name }} {
+    const LEN: usize = core::mem::size_of::<{{ state_name }}>();
+}
+
+impl {{ state_name }} {
+    pub const SEED: &'static str = "init";
+
+    pub fn validate_pda(bump: u8, pda: &Pubkey, owner: &Pubkey) -> Result<(), ProgramError> {
+        let seed_with_bump = &[Self::SEED.as_bytes(), owner, &[bump]];
+        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn initialize(state_acc: &AccountInfo, ix_data: &{{ instruction_type }}) -> ProgramResult {
+        let state =
+            unsafe { load_acc_mut_unchecked::<{{ state_name }}>(state_acc.borrow_mut_data_unchecked()) }?;
+
+        state.owner = ix_data.owner;
+        Ok(())
+    }
+}
+{% endif %}
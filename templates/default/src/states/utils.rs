@@ -0,0 +1,43 @@
+{% if pinocchio_series == "0.10" %}
+use pinocchio::error::ProgramError;
+{% else %}
+use pinocchio::program_error::ProgramError;
+{% endif %}
+
+use crate::errors::MyProgramError;
+
+pub trait DataLen {
+    const LEN: usize;
+}
+
+#[inline(always)]
+pub unsafe fn load_acc_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(&*(bytes.as_ptr() as *const T))
+}
+
+#[inline(always)]
+pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(&mut *(bytes.as_mut_ptr() as *mut T))
+}
+
+#[inline(always)]
+pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
+    Ok(&*(bytes.as_ptr() as *const T))
+}
+
+pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
+    core::slice::from_raw_parts(data as *const T as *const u8, T::LEN)
+}
+
+pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
+    core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
+}
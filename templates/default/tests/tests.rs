@@ -0,0 +1,182 @@
+{% if test_framework == "litesvm" %}
+use litesvm::LiteSVM;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+{% if pinocchio_series == "0.10" %}
+use solana_sdk_ids::{system_program, sysvar};
+{% else %}
+use solana_sdk::{system_program, sysvar};
+{% endif %}
+
+use {{ lib_name }}::instructions::{{ instruction_type }};
+use {{ lib_name }}::states::{to_bytes, DataLen, {{ state_name }}};
+
+pub const PROGRAM: Pubkey = pubkey!("{{ program_address }}");
+
+pub fn setup() -> (LiteSVM, Keypair) {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(PROGRAM, "target/deploy/{{ lib_name }}.so")
+        .expect("run `pinoc build` before the tests");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    (svm, payer)
+}
+
+#[test]
+fn test_{{ instruction_name }}() {
+    let (mut svm, payer) = setup();
+
+    // Create the PDA
+    let (state_pda, bump) = Pubkey::find_program_address(
+        &[{{ state_name }}::SEED.as_bytes(), payer.pubkey().as_ref()],
+        &PROGRAM,
+    );
+
+    // Create the instruction data
+    let ix_data = {{ instruction_type }} {
+{% if pinocchio_series == "0.10" %}
+        owner: payer.pubkey(),
+{% else %}
+        owner: payer.pubkey().to_bytes(),
+{% endif %}
+        bump,
+    };
+
+    // Ix discriminator = 0
+    let mut ser_ix_data = vec![0];
+
+    // Serialize the instruction data
+    ser_ix_data.extend_from_slice(unsafe { to_bytes(&ix_data) });
+
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ser_ix_data,
+        vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(state_pda, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+
+    let state_account = svm.get_account(&state_pda).unwrap();
+    assert_eq!(state_account.owner, PROGRAM);
+    assert_eq!(state_account.data.len(), {{ state_name }}::LEN);
+}
+{% else %}
+use mollusk_svm::result::{Check, ProgramResult};
+use mollusk_svm::{program, Mollusk};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+extern crate alloc;
+use alloc::vec;
+
+use {{ lib_name }}::instructions::{{ instruction_type }};
+use {{ lib_name }}::states::{to_bytes, {{ state_name }}};
+{% if pinocchio_series != "0.10" %}
+use solana_sdk::rent::Rent;
+use solana_sdk::sysvar::Sysvar;
+{% endif %}
+
+pub const PROGRAM: Pubkey = pubkey!("{{ program_address }}");
+
+pub const RENT: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
+
+pub const PAYER: Pubkey = pubkey!("{{ user_address }}");
+
+pub fn mollusk() -> Mollusk {
+    let mollusk = Mollusk::new(&PROGRAM, "target/deploy/{{ lib_name }}");
+    mollusk
+}
+{% if pinocchio_series != "0.10" %}
+
+pub fn get_rent_data() -> Vec<u8> {
+    let rent = Rent::default();
+    unsafe {
+        core::slice::from_raw_parts(&rent as *const Rent as *const u8, Rent::size_of()).to_vec()
+    }
+}
+{% endif %}
+
+#[test]
+fn test_{{ instruction_name }}() {
+    let mollusk = mollusk();
+
+    //system program and system account
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Create the PDA
+    let (state_pda, bump) = Pubkey::find_program_address(
+        &[{{ state_name }}::SEED.as_bytes(), &PAYER.to_bytes()],
+        &PROGRAM,
+    );
+
+    //Initialize the accounts
+    let payer_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let state_account = Account::new(0, 0, &system_program);
+{% if pinocchio_series == "0.10" %}
+    let (_, rent_account) = mollusk.sysvars.keyed_account_for_rent_sysvar();
+{% else %}
+    let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
+    let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
+    rent_account.data = get_rent_data();
+{% endif %}
+
+    //Push the accounts in to the instruction_accounts vec!
+    let ix_accounts = vec![
+        AccountMeta::new(PAYER, true),
+        AccountMeta::new(state_pda, false),
+        AccountMeta::new_readonly(RENT, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    // Create the instruction data
+    let ix_data = {{ instruction_type }} {
+{% if pinocchio_series == "0.10" %}
+        owner: PAYER,
+{% else %}
+        owner: *PAYER.as_array(),
+{% endif %}
+        bump,
+    };
+
+    // Ix discriminator = 0
+    let mut ser_ix_data = vec![0];
+
+    // Serialize the instruction data
+    ser_ix_data.extend_from_slice(unsafe { to_bytes(&ix_data) });
+
+    // Create instruction
+    let instruction = Instruction::new_with_bytes(PROGRAM, &ser_ix_data, ix_accounts);
+
+    // Create tx_accounts vec
+    let tx_accounts = &vec![
+        (PAYER, payer_account.clone()),
+        (state_pda, state_account.clone()),
+        (RENT, rent_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    let init_res =
+        mollusk.process_and_validate_instruction(&instruction, tx_accounts, &[Check::success()]);
+
+    assert!(init_res.program_result == ProgramResult::Success);
+}
+{% endif %}
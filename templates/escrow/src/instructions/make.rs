@@ -0,0 +1,99 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::MyProgramError,
+    states::{
+        utils::{load_ix_data, DataLen},
+        Escrow,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Make {
+    pub seed: [u8; 8],
+    pub deposit: [u8; 8],
+    pub receive: [u8; 8],
+    pub bump: u8,
+}
+
+impl DataLen for Make {
+    const LEN: usize = core::mem::size_of::<Make>();
+}
+
+pub fn make(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [maker_acc, escrow_acc, mint_a_acc, mint_b_acc, maker_ata_a_acc, vault_acc, _system_program, _token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !maker_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !escrow_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let ix_data = unsafe { load_ix_data::<Make>(data)? };
+    let deposit = u64::from_le_bytes(ix_data.deposit);
+    let receive = u64::from_le_bytes(ix_data.receive);
+    if deposit == 0 || receive == 0 {
+        return Err(MyProgramError::InvalidAmount.into());
+    }
+
+    Escrow::validate_pda(ix_data.bump, escrow_acc.key(), maker_acc.key(), &ix_data.seed)?;
+
+    {
+        let vault = TokenAccount::from_account_info(vault_acc)?;
+        if vault.owner() != escrow_acc.key() || vault.mint() != mint_a_acc.key() {
+            return Err(MyProgramError::InvalidVault.into());
+        }
+    }
+
+    let bump_bytes = [ix_data.bump];
+    let signer_seeds = [
+        Seed::from(Escrow::SEED.as_bytes()),
+        Seed::from(maker_acc.key()),
+        Seed::from(&ix_data.seed[..]),
+        Seed::from(&bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    CreateAccount {
+        from: maker_acc,
+        to: escrow_acc,
+        space: Escrow::LEN as u64,
+        owner: &crate::ID,
+        lamports: Rent::get()?.minimum_balance(Escrow::LEN),
+    }
+    .invoke_signed(&signers)?;
+
+    Escrow::initialize(
+        escrow_acc,
+        maker_acc.key(),
+        mint_a_acc.key(),
+        mint_b_acc.key(),
+        receive,
+        ix_data.seed,
+        ix_data.bump,
+    )?;
+
+    Transfer {
+        from: maker_ata_a_acc,
+        to: vault_acc,
+        authority: maker_acc,
+        amount: deposit,
+    }
+    .invoke()
+}
@@ -0,0 +1,29 @@
+use pinocchio::program_error::ProgramError;
+
+pub mod make;
+pub mod refund;
+pub mod take;
+
+pub use make::*;
+pub use refund::*;
+pub use take::*;
+
+#[repr(u8)]
+pub enum ProgramInstruction {
+    Make,
+    Take,
+    Refund,
+}
+
+impl TryFrom<&u8> for ProgramInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProgramInstruction::Make),
+            1 => Ok(ProgramInstruction::Take),
+            2 => Ok(ProgramInstruction::Refund),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
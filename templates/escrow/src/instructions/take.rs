@@ -0,0 +1,84 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use pinocchio_token::{
+    instructions::{CloseAccount, Transfer},
+    state::TokenAccount,
+};
+
+use crate::{
+    errors::MyProgramError,
+    states::{utils::load_acc_unchecked, Escrow},
+};
+
+pub fn take(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [taker_acc, maker_acc, escrow_acc, taker_ata_a_acc, taker_ata_b_acc, maker_ata_b_acc, vault_acc, _token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !taker_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !escrow_acc.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let escrow = unsafe { *load_acc_unchecked::<Escrow>(&escrow_acc.try_borrow_data()?)? };
+    escrow.validate(escrow_acc, maker_acc.key())?;
+
+    {
+        let maker_ata_b = TokenAccount::from_account_info(maker_ata_b_acc)?;
+        if maker_ata_b.owner() != maker_acc.key() || maker_ata_b.mint() != &escrow.mint_b {
+            return Err(MyProgramError::InvalidMint.into());
+        }
+    }
+
+    let amount = {
+        let vault = TokenAccount::from_account_info(vault_acc)?;
+        if vault.owner() != escrow_acc.key() || vault.mint() != &escrow.mint_a {
+            return Err(MyProgramError::InvalidVault.into());
+        }
+        vault.amount()
+    };
+
+    Transfer {
+        from: taker_ata_b_acc,
+        to: maker_ata_b_acc,
+        authority: taker_acc,
+        amount: escrow.receive,
+    }
+    .invoke()?;
+
+    let bump_bytes = [escrow.bump];
+    let signer_seeds = [
+        Seed::from(Escrow::SEED.as_bytes()),
+        Seed::from(&escrow.maker),
+        Seed::from(&escrow.seed[..]),
+        Seed::from(&bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    Transfer {
+        from: vault_acc,
+        to: taker_ata_a_acc,
+        authority: escrow_acc,
+        amount,
+    }
+    .invoke_signed(&signers)?;
+
+    CloseAccount {
+        account: vault_acc,
+        destination: maker_acc,
+        authority: escrow_acc,
+    }
+    .invoke_signed(&signers)?;
+
+    Escrow::close(escrow_acc, maker_acc)
+}
@@ -0,0 +1,79 @@
+use super::utils::{load_acc_mut_unchecked, DataLen};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use crate::errors::MyProgramError;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Escrow {
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub seed: [u8; 8],
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl DataLen for Escrow {
+    const LEN: usize = core::mem::size_of::<Escrow>();
+}
+
+impl Escrow {
+    pub const SEED: &'static str = "escrow";
+
+    pub fn validate_pda(
+        bump: u8,
+        pda: &Pubkey,
+        maker: &Pubkey,
+        seed: &[u8; 8],
+    ) -> Result<(), ProgramError> {
+        let seed_with_bump = &[Self::SEED.as_bytes(), maker, seed, &[bump]];
+        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn initialize(
+        escrow_acc: &AccountInfo,
+        maker: &Pubkey,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+        receive: u64,
+        seed: [u8; 8],
+        bump: u8,
+    ) -> ProgramResult {
+        let escrow =
+            unsafe { load_acc_mut_unchecked::<Escrow>(escrow_acc.borrow_mut_data_unchecked()) }?;
+
+        escrow.maker = *maker;
+        escrow.mint_a = *mint_a;
+        escrow.mint_b = *mint_b;
+        escrow.receive = receive;
+        escrow.seed = seed;
+        escrow.bump = bump;
+        Ok(())
+    }
+
+    /// Checks that the escrow account is the PDA of `maker` described by this state.
+    pub fn validate(&self, escrow_acc: &AccountInfo, maker: &Pubkey) -> ProgramResult {
+        if self.maker.ne(maker) {
+            return Err(MyProgramError::InvalidMaker.into());
+        }
+        Self::validate_pda(self.bump, escrow_acc.key(), maker, &self.seed)
+    }
+
+    /// Moves the rent of the escrow account to `destination` and closes it.
+    pub fn close(escrow_acc: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        *destination.try_borrow_mut_lamports()? += escrow_acc.lamports();
+        *escrow_acc.try_borrow_mut_lamports()? = 0;
+        escrow_acc.close()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod escrow;
+pub mod utils;
+
+pub use escrow::*;
+pub use utils::*;
@@ -0,0 +1,14 @@
+use pinocchio::program_error::ProgramError;
+
+#[derive(Clone, PartialEq)]
+pub enum MyProgramError {
+    InvalidInstructionData,
+    InvalidTokenProgram,
+    InvalidAmount,
+}
+
+impl From<MyProgramError> for ProgramError {
+    fn from(e: MyProgramError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
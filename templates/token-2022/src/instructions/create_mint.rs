@@ -0,0 +1,57 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::MyProgramError,
+    states::utils::{load_ix_data, DataLen},
+    token_2022::{InitializeMint2, MINT_LEN, TOKEN_2022_PROGRAM_ID},
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CreateMint {
+    pub decimals: u8,
+}
+
+impl DataLen for CreateMint {
+    const LEN: usize = core::mem::size_of::<CreateMint>();
+}
+
+pub fn create_mint(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer_acc, mint_acc, _system_program, token_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer_acc.is_signer() || !mint_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if token_program.key() != &TOKEN_2022_PROGRAM_ID {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let ix_data = unsafe { load_ix_data::<CreateMint>(data)? };
+
+    CreateAccount {
+        from: payer_acc,
+        to: mint_acc,
+        space: MINT_LEN as u64,
+        owner: &TOKEN_2022_PROGRAM_ID,
+        lamports: Rent::get()?.minimum_balance(MINT_LEN),
+    }
+    .invoke()?;
+
+    InitializeMint2 {
+        mint: mint_acc,
+        decimals: ix_data.decimals,
+        mint_authority: payer_acc.key(),
+        freeze_authority: None,
+    }
+    .invoke()
+}
@@ -0,0 +1,45 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    errors::MyProgramError,
+    states::utils::{load_ix_data, DataLen},
+    token_2022::{MintTo, TOKEN_2022_PROGRAM_ID},
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MintToArgs {
+    pub amount: [u8; 8],
+}
+
+impl DataLen for MintToArgs {
+    const LEN: usize = core::mem::size_of::<MintToArgs>();
+}
+
+pub fn mint_to(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority_acc, mint_acc, destination_acc, token_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if token_program.key() != &TOKEN_2022_PROGRAM_ID {
+        return Err(MyProgramError::InvalidTokenProgram.into());
+    }
+
+    let ix_data = unsafe { load_ix_data::<MintToArgs>(data)? };
+    let amount = u64::from_le_bytes(ix_data.amount);
+    if amount == 0 {
+        return Err(MyProgramError::InvalidAmount.into());
+    }
+
+    MintTo {
+        mint: mint_acc,
+        account: destination_acc,
+        mint_authority: authority_acc,
+        amount,
+    }
+    .invoke()
+}
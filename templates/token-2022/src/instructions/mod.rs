@@ -0,0 +1,25 @@
+use pinocchio::program_error::ProgramError;
+
+pub mod create_mint;
+pub mod mint_to;
+
+pub use create_mint::*;
+pub use mint_to::*;
+
+#[repr(u8)]
+pub enum ProgramInstruction {
+    CreateMint,
+    MintTo,
+}
+
+impl TryFrom<&u8> for ProgramInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProgramInstruction::CreateMint),
+            1 => Ok(ProgramInstruction::MintTo),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+#![no_std]
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod errors;
+pub mod instructions;
+pub mod states;
+pub mod token_2022;
+
+pinocchio_pubkey::declare_id!("{{ program_address }}");
@@ -0,0 +1,85 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Size of a mint account without extensions.
+pub const MINT_LEN: usize = 82;
+
+/// Initializes a mint account that was already created and assigned to Token-2022.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeMint2<'a> {
+    pub mint: &'a AccountInfo,
+    pub decimals: u8,
+    pub mint_authority: &'a Pubkey,
+    pub freeze_authority: Option<&'a Pubkey>,
+}
+
+impl InitializeMint2<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        // discriminator, decimals, mint authority, freeze authority option
+        let mut data = [0u8; 67];
+        data[0] = 20;
+        data[1] = self.decimals;
+        data[2..34].copy_from_slice(self.mint_authority);
+        let len = match self.freeze_authority {
+            Some(freeze_authority) => {
+                data[34] = 1;
+                data[35..67].copy_from_slice(freeze_authority);
+                67
+            }
+            None => 35,
+        };
+
+        let account_metas = [AccountMeta::writable(self.mint.key())];
+        let instruction = Instruction {
+            program_id: &TOKEN_2022_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &data[..len],
+        };
+
+        invoke(&instruction, &[self.mint])
+    }
+}
+
+/// Mints new tokens to a token account.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[WRITE]` The account to mint tokens to.
+///   2. `[SIGNER]` The mint's minting authority.
+pub struct MintTo<'a> {
+    pub mint: &'a AccountInfo,
+    pub account: &'a AccountInfo,
+    pub mint_authority: &'a AccountInfo,
+    pub amount: u64,
+}
+
+impl MintTo<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let mut data = [0u8; 9];
+        data[0] = 7;
+        data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+
+        let account_metas = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly_signer(self.mint_authority.key()),
+        ];
+        let instruction = Instruction {
+            program_id: &TOKEN_2022_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &data,
+        };
+
+        invoke(&instruction, &[self.mint, self.account, self.mint_authority])
+    }
+}
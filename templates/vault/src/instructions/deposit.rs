@@ -0,0 +1,34 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    errors::MyProgramError,
+    instructions::AmountArgs,
+    states::{utils::load_ix_data, Vault},
+};
+
+pub fn deposit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [owner_acc, vault_acc, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let ix_data = unsafe { load_ix_data::<AmountArgs>(data)? };
+    let amount = ix_data.amount();
+    if amount == 0 {
+        return Err(MyProgramError::InvalidAmount.into());
+    }
+
+    Vault::validate_pda(ix_data.bump, vault_acc.key(), owner_acc.key())?;
+
+    Transfer {
+        from: owner_acc,
+        to: vault_acc,
+        lamports: amount,
+    }
+    .invoke()
+}
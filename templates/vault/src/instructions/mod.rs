@@ -0,0 +1,45 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::states::utils::DataLen;
+
+pub mod deposit;
+pub mod withdraw;
+
+pub use deposit::*;
+pub use withdraw::*;
+
+#[repr(u8)]
+pub enum ProgramInstruction {
+    Deposit,
+    Withdraw,
+}
+
+impl TryFrom<&u8> for ProgramInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            0 => Ok(ProgramInstruction::Deposit),
+            1 => Ok(ProgramInstruction::Withdraw),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Instruction data shared by `deposit` and `withdraw`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AmountArgs {
+    pub amount: [u8; 8],
+    pub bump: u8,
+}
+
+impl DataLen for AmountArgs {
+    const LEN: usize = core::mem::size_of::<AmountArgs>();
+}
+
+impl AmountArgs {
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+}
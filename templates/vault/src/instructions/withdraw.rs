@@ -0,0 +1,50 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    errors::MyProgramError,
+    instructions::AmountArgs,
+    states::{utils::load_ix_data, Vault},
+};
+
+pub fn withdraw(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [owner_acc, vault_acc, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner_acc.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let ix_data = unsafe { load_ix_data::<AmountArgs>(data)? };
+    let amount = ix_data.amount();
+    if amount == 0 {
+        return Err(MyProgramError::InvalidAmount.into());
+    }
+    if vault_acc.lamports() < amount {
+        return Err(MyProgramError::InsufficientFunds.into());
+    }
+
+    Vault::validate_pda(ix_data.bump, vault_acc.key(), owner_acc.key())?;
+
+    let bump_bytes = [ix_data.bump];
+    let signer_seeds = [
+        Seed::from(Vault::SEED.as_bytes()),
+        Seed::from(owner_acc.key()),
+        Seed::from(&bump_bytes[..]),
+    ];
+    let signers = [Signer::from(&signer_seeds[..])];
+
+    Transfer {
+        from: vault_acc,
+        to: owner_acc,
+        lamports: amount,
+    }
+    .invoke_signed(&signers)
+}
@@ -0,0 +1,5 @@
+pub mod utils;
+pub mod vault;
+
+pub use utils::*;
+pub use vault::*;
@@ -0,0 +1,22 @@
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+};
+
+use crate::errors::MyProgramError;
+
+/// The vault is a system account holding lamports, so it has no data layout.
+pub struct Vault;
+
+impl Vault {
+    pub const SEED: &'static str = "vault";
+
+    pub fn validate_pda(bump: u8, pda: &Pubkey, owner: &Pubkey) -> Result<(), ProgramError> {
+        let seed_with_bump = &[Self::SEED.as_bytes(), owner, &[bump]];
+        let derived = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
+        if derived != *pda {
+            return Err(MyProgramError::PdaMismatch.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use mollusk_svm::result::{Check, ProgramResult};
+use mollusk_svm::{program, Mollusk};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use {{ lib_name }}::states::Vault;
+
+pub const PROGRAM: Pubkey = pubkey!("{{ program_address }}");
+
+pub const OWNER: Pubkey = pubkey!("{{ user_address }}");
+
+pub fn mollusk() -> Mollusk {
+    Mollusk::new(&PROGRAM, "target/deploy/{{ lib_name }}")
+}
+
+fn instruction(discriminator: u8, vault: Pubkey, amount: u64, bump: u8) -> Instruction {
+    let (system_program, _) = program::keyed_account_for_system_program();
+
+    let mut data = vec![discriminator];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(bump);
+
+    Instruction::new_with_bytes(
+        PROGRAM,
+        &data,
+        vec![
+            AccountMeta::new(OWNER, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    )
+}
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (vault, bump) =
+        Pubkey::find_program_address(&[Vault::SEED.as_bytes(), &OWNER.to_bytes()], &PROGRAM);
+
+    let owner_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+    let vault_account = Account::new(0, 0, &system_program);
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction(0, vault, LAMPORTS_PER_SOL, bump),
+        &[
+            (OWNER, owner_account),
+            (vault, vault_account),
+            (system_program, system_account.clone()),
+        ],
+        &[Check::success(), Check::account(&vault).lamports(LAMPORTS_PER_SOL).build()],
+    );
+    assert!(result.program_result == ProgramResult::Success);
+
+    let owner_account = result.get_account(&OWNER).unwrap().clone();
+    let vault_account = result.get_account(&vault).unwrap().clone();
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction(1, vault, LAMPORTS_PER_SOL / 2, bump),
+        &[
+            (OWNER, owner_account),
+            (vault, vault_account),
+            (system_program, system_account),
+        ],
+        &[Check::success(), Check::account(&vault).lamports(LAMPORTS_PER_SOL / 2).build()],
+    );
+    assert!(result.program_result == ProgramResult::Success);
+}